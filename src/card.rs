@@ -0,0 +1,140 @@
+//! Content framed by a padded, bordered card that fills the screen.
+
+use crate::layout::{self, with_content_width};
+use crate::{output, text};
+use crossterm::cursor::MoveTo;
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor};
+use crossterm::Command;
+use std::fmt;
+
+/// A command that draws a card filling the screen, inset by a margin, then renders content inside it.
+///
+/// The card's background is filled with [`background`](Card::background) if it's set,
+/// its edge is drawn with a border if [`border`](Card::border) is `true`,
+/// and there's [`padding`](Card::padding) between the edge and the content on each side.
+/// Typewriter text inside the card is wrapped to its inner width and starts each line at its inner left edge,
+/// the same way it is within a content width (see [`Presentation::content_width`](crate::Presentation::content_width)).
+/// The content starts at the card's inner top left corner, and the cursor ends up on the line after the card.
+/// Content that's taller than the card isn't cut off, and content that resets colors stops using its background.
+/// If the terminal is too small for the card, a message saying so is printed instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::crossterm::style::Color;
+/// use clp::{slide, Card, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// let mut card = Card::new(TypewriterPrint(
+///     "Every slide of this talk is framed the same way.",
+///     Duration::from_millis(25),
+/// ));
+///
+/// card.background = Some(Color::DarkBlue);
+///
+/// slide!(card).expect("the text should be typed inside the card");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`Card`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Card<C: Command> {
+    /// The content rendered inside the card.
+    pub content: C,
+    /// The number of rows and columns between the edges of the terminal and the card.
+    pub margin: u16,
+    /// The number of rows and columns between the card's edge and its content.
+    pub padding: u16,
+    /// The color the card is filled with, or [`None`] to keep the terminal's background.
+    pub background: Option<Color>,
+    /// Whether a border is drawn around the card's edge.
+    pub border: bool,
+}
+
+impl<C: Command> Card<C> {
+    /// Creates a bordered card without a background, with a margin of 1 and a padding of 1.
+    pub fn new(content: C) -> Self {
+        Self {
+            content,
+            margin: 1,
+            padding: 1,
+            background: None,
+            border: true,
+        }
+    }
+
+    /// Returns the number of rows and columns between the card's outer edge and its content.
+    fn inset(&self) -> u16 {
+        u16::from(self.border).saturating_add(self.padding)
+    }
+
+    /// Returns the smallest number of columns and rows the terminal needs for the card,
+    /// leaving room for at least one column and row of content.
+    pub fn minimum_size(&self) -> (u16, u16) {
+        let size = self
+            .margin
+            .saturating_add(self.inset())
+            .saturating_mul(2)
+            .saturating_add(1);
+
+        (size, size)
+    }
+}
+
+impl<C: Command> Command for Card<C> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let (minimum_columns, minimum_rows) = self.minimum_size();
+
+        if layout::write_too_small(f, minimum_columns, minimum_rows)? {
+            return text::write_newline(f);
+        }
+
+        let (columns, rows) = output::terminal_size().unwrap_or((80, 24));
+        let width = columns - self.margin * 2;
+        let height = rows - self.margin * 2;
+        let inner = width.saturating_sub(2) as usize;
+
+        if let Some(background) = self.background {
+            SetBackgroundColor(background).write_ansi(f)?;
+        }
+
+        for row in 0..height {
+            MoveTo(self.margin, self.margin + row).write_ansi(f)?;
+
+            let line = match (self.border, row) {
+                (false, _) => " ".repeat(width as usize),
+                (true, 0) => format!("┌{}┐", "─".repeat(inner)),
+                (true, row) if row == height - 1 => format!("└{}┘", "─".repeat(inner)),
+                (true, _) => format!("│{}│", " ".repeat(inner)),
+            };
+
+            Print(line).write_ansi(f)?;
+        }
+
+        let inset = self.inset();
+
+        MoveTo(self.margin + inset, self.margin + inset).write_ansi(f)?;
+
+        // The card is centered, so centering the content within its inner width lines it up with the inside of the card.
+        with_content_width(Some(width - inset * 2), || self.content.write_ansi(f))?;
+
+        if self.background.is_some() {
+            ResetColor.write_ansi(f)?;
+        }
+
+        MoveTo(0, self.margin + height).write_ansi(f)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute Card command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,172 @@
+//! Content centered on the screen.
+
+use crate::layout::{self, with_content_width};
+use crate::markers::{self, Segment};
+use crate::typing::typewrite;
+use crate::{estimate, output, text, Typing};
+use crossterm::cursor::MoveTo;
+use crossterm::Command;
+use std::fmt::{self, Display};
+use std::io::sink;
+
+/// A command that types content in the middle of the screen, centered both vertically and horizontally.
+///
+/// The content is measured against the size of the terminal,
+/// so that the block of lines as a whole is centered vertically, and each line is centered horizontally.
+/// Wide characters, pause markers and ANSI escape sequences are taken into account when measuring lines.
+/// Lines aren't wrapped, so they should fit within the terminal.
+/// The content is typed with the slide's typing options, see [`Typing::inherit`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # #![allow(deprecated)]
+/// use clp::{slide, CenteredScreen};
+///
+/// slide!(CenteredScreen("The Future of Terminals\nA talk by Ferris"))
+///     .expect("the title card should be typed in the middle of the screen");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`CenteredScreen`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+///
+/// This command is deprecated in favour of [`Centered`], which centers any command.
+/// `CenteredScreen(text)` is typed the same way as `Centered(TypewriterPrint(text, Typing::inherit()))`,
+/// except that [`Centered`] lines each line up with the left edge of the block instead of centering them separately.
+#[deprecated(note = "use `Centered(TypewriterPrint(text, Typing::inherit()))` instead")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CenteredScreen<T: Display>(pub T);
+
+#[allow(deprecated)]
+impl<T: Display> Command for CenteredScreen<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let content = text::normalize_newlines(&self.0.to_string()).into_owned();
+        let lines: Vec<&str> = content.split('\n').map(str::trim_end).collect();
+        let (columns, rows) = output::terminal_size().unwrap_or((80, 24));
+        let height = u16::try_from(lines.len()).unwrap_or(u16::MAX);
+        let top = rows.saturating_sub(height) / 2;
+        // The lines are already laid out, so they shouldn't be wrapped and centered again while typing.
+        with_content_width(None, || {
+            lines.iter().enumerate().try_for_each(|(index, line)| {
+                let line_width = u16::try_from(visible_width(line)).unwrap_or(columns);
+
+                MoveTo(
+                    columns.saturating_sub(line_width) / 2,
+                    top.saturating_add(index as u16),
+                )
+                .write_ansi(f)?;
+                typewrite(f, line, None, Typing::inherit())
+            })
+        })
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute CenteredScreen command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// A command that renders other content as a block in the middle of the screen, centered both vertically and horizontally.
+///
+/// The content is rendered once without delays or interactions first, to measure its widest line and how many lines it has,
+/// then it's rendered for real starting at the top left corner of the block.
+/// Typewriter text starts each of its lines at the left edge of the block,
+/// the same way it does within a content width (see [`Presentation::content_width`](crate::Presentation::content_width)),
+/// so lines of different widths stay aligned with each other.
+/// Content that's wider or taller than the terminal starts at its left or top edge instead.
+/// Any command can be centered this way,
+/// but content that moves the cursor around by itself isn't measured correctly.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, Centered, TypewriterPrint, Typing};
+///
+/// slide!(Centered(TypewriterPrint(
+///     "The Future of Terminals\nA talk by Ferris",
+///     Typing::inherit(),
+/// )))
+/// .expect("the title card should be typed in the middle of the screen");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`Centered`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Centered<C: Command>(pub C);
+
+impl<C: Command> Centered<C> {
+    /// Returns the number of columns and rows the content takes up once it's rendered.
+    fn measure(&self) -> (u16, u16) {
+        let mut rendered = String::new();
+
+        // Measuring isn't part of the slide, so it shouldn't count towards how long the slide is estimated to take.
+        with_content_width(None, || {
+            estimate::measure(|| {
+                output::render_headless(Box::new(sink()), || {
+                    let _ = self.0.write_ansi(&mut rendered);
+                });
+            });
+        });
+
+        let rendered = text::strip_escapes(&rendered).replace('\r', "");
+        let lines: Vec<&str> = rendered.trim_end_matches('\n').split('\n').collect();
+        let width = lines
+            .iter()
+            .map(|line| layout::width(line.trim_end()))
+            .max()
+            .unwrap_or(0);
+
+        (
+            u16::try_from(width).unwrap_or(u16::MAX),
+            u16::try_from(lines.len()).unwrap_or(u16::MAX),
+        )
+    }
+}
+
+impl<C: Command> Command for Centered<C> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let (width, height) = self.measure();
+        let (columns, rows) = output::terminal_size().unwrap_or((80, 24));
+        let left = columns.saturating_sub(width) / 2;
+        let top = rows.saturating_sub(height) / 2;
+
+        MoveTo(left, top).write_ansi(f)?;
+
+        // Centering the content within its own width lines each of its lines up with the left edge of the block.
+        with_content_width(Some(width.clamp(1, columns.max(1))), || {
+            self.0.write_ansi(f)
+        })
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute Centered command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Returns how many columns `line` takes up once it's typed, without its pause markers and escape sequences.
+fn visible_width(line: &str) -> usize {
+    text::split_escapes(markers::parse(line))
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Text(content) => layout::width(&content),
+            _ => 0,
+        })
+        .sum()
+}
@@ -0,0 +1,132 @@
+//! Simple charts drawn with block characters.
+
+use crate::{output, text};
+use crossterm::style::{Color, PrintStyledContent, Stylize};
+use crossterm::Command;
+use std::fmt::{self, Display};
+
+/// Block characters for the fractional part of a bar, in eighths.
+const PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// A command that draws a horizontal bar chart.
+///
+/// Each bar is labeled and followed by its value.
+/// Bars are scaled so that the largest absolute value is `max_width` columns wide,
+/// or narrower if the terminal can't fit that.
+/// Negative values are drawn with a lighter shade, and zero (or non-finite) values have no bar at all.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{crossterm, slide, BarChart};
+/// use crossterm::style::Color;
+///
+/// let mut chart = BarChart::new(vec![("Rust", 87.0), ("C++", 48.5), ("Go", 32.2)], 40);
+///
+/// chart.color = Some(Color::DarkYellow);
+///
+/// slide!(chart).expect("the bar chart should be drawn");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`BarChart`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarChart<L: Display> {
+    /// The label and value of each bar.
+    pub bars: Vec<(L, f64)>,
+    /// The maximum width of a bar, in columns.
+    pub max_width: u16,
+    /// The color of the bars, or [`None`] to use the current foreground color.
+    pub color: Option<Color>,
+}
+
+impl<L: Display> BarChart<L> {
+    /// Creates an uncolored bar chart.
+    pub fn new(bars: Vec<(L, f64)>, max_width: u16) -> Self {
+        Self {
+            bars,
+            max_width,
+            color: None,
+        }
+    }
+}
+
+impl<L: Display> Command for BarChart<L> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let labels: Vec<String> = self
+            .bars
+            .iter()
+            .map(|(label, _)| label.to_string())
+            .collect();
+        let values: Vec<String> = self
+            .bars
+            .iter()
+            .map(|(_, value)| value.to_string())
+            .collect();
+        let label_width = labels
+            .iter()
+            .map(|label| label.chars().count())
+            .max()
+            .unwrap_or(0);
+        let value_width = values
+            .iter()
+            .map(|value| value.chars().count())
+            .max()
+            .unwrap_or(0);
+        let terminal_width =
+            output::terminal_size().map_or(u16::MAX, |(columns, _)| columns) as usize;
+        let bar_width = (self.max_width as usize)
+            .min(terminal_width.saturating_sub(label_width + value_width + 2))
+            .max(1);
+        let max_value = self
+            .bars
+            .iter()
+            .map(|(_, value)| value.abs())
+            .filter(|value| value.is_finite())
+            .fold(0.0, f64::max);
+
+        for ((label, value_text), (_, value)) in labels.iter().zip(&values).zip(&self.bars) {
+            let eighths = if max_value > 0.0 && value.is_finite() {
+                (value.abs() / max_value * bar_width as f64 * 8.0).round() as usize
+            } else {
+                0
+            };
+            let bar: String = if *value < 0.0 {
+                "░".repeat(eighths.div_ceil(8))
+            } else {
+                let mut bar = "█".repeat(eighths / 8);
+
+                if eighths % 8 > 0 {
+                    bar.push(PARTIAL_BLOCKS[eighths % 8]);
+                }
+
+                bar
+            };
+
+            write!(f, "{label:<label_width$} ")?;
+
+            match self.color {
+                Some(color) => PrintStyledContent(bar.as_str().with(color)).write_ansi(f)?,
+                None => f.write_str(&bar)?,
+            }
+
+            write!(f, " {value_text}")?;
+            text::write_newline(f)?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute BarChart command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,109 @@
+//! Checklists that are checked off one item at a time.
+
+use crate::layout::{self, content_area};
+use crate::typing::{pause, typewrite};
+use crate::{text, Typing, WaitForInteraction};
+use crossterm::cursor::{MoveToColumn, MoveToNextLine, MoveToPreviousLine};
+use crossterm::style::{Print, Stylize};
+use crossterm::Command;
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+/// When a [`Checklist`] checks off its next item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Advance {
+    /// After an interaction, like [`WaitForInteraction`].
+    #[default]
+    Interaction,
+    /// After the given duration.
+    After(Duration),
+}
+
+/// A command that types a list of unchecked items, like `[ ] task`,
+/// then checks them off one at a time with a green `[x]`.
+///
+/// Each item is typed on its own line,
+/// and checking an item off only rewrites its checkbox, leaving the rest of the list alone.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, Advance, Checklist};
+/// use std::time::Duration;
+///
+/// slide!(Checklist::new(
+///     vec!["Install the toolchain", "Create a project", "Run it"],
+///     Advance::After(Duration::from_secs(1)),
+/// ))
+/// .expect("each item should be checked off a second after the previous one");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`Checklist`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checklist<T: Display> {
+    /// The items of the list.
+    pub items: Vec<T>,
+    /// When each item is checked off.
+    pub advance: Advance,
+    /// How the list is typed before any item is checked off.
+    pub typing: Typing,
+}
+
+impl<T: Display> Checklist<T> {
+    /// Creates a checklist that's typed with the slide's typing options, see [`Typing::inherit`].
+    pub fn new(items: Vec<T>, advance: Advance) -> Self {
+        Self {
+            items,
+            advance,
+            typing: Typing::inherit(),
+        }
+    }
+}
+
+impl<T: Display> Command for Checklist<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let lines: Vec<String> = self
+            .items
+            .iter()
+            .map(|item| format!("[ ] {item}"))
+            .collect();
+
+        for line in &lines {
+            typewrite(f, line, None, self.typing)?;
+            text::write_newline(f)?;
+        }
+
+        let (padding, _) = content_area();
+        let mut rows_below: u16 = lines.iter().map(|line| layout::rows(line)).sum();
+
+        for line in &lines {
+            match self.advance {
+                Advance::Interaction => WaitForInteraction.write_ansi(f)?,
+                Advance::After(duration) => pause(duration),
+            }
+
+            MoveToPreviousLine(rows_below).write_ansi(f)?;
+            MoveToColumn(padding).write_ansi(f)?;
+            Print("[x]".green()).write_ansi(f)?;
+            MoveToNextLine(rows_below).write_ansi(f)?;
+
+            rows_below -= layout::rows(line);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute Checklist command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,83 @@
+//! Restoring the terminal to a clean state.
+
+use crate::output::output;
+use crossterm::cursor::Show;
+use crossterm::event::{DisableBracketedPaste, DisableFocusChange, DisableMouseCapture};
+use crossterm::execute;
+use crossterm::style::{Attribute, ResetColor, SetAttribute};
+use crossterm::terminal::{disable_raw_mode, EnableLineWrap, LeaveAlternateScreen};
+use std::io;
+use std::panic;
+use std::sync::Once;
+
+/// Unconditionally restores the terminal to a clean state.
+///
+/// This disables raw mode, shows the cursor, resets colors and attributes, leaves the alternate screen,
+/// re-enables line wrapping and disables mouse capture, focus change reporting and bracketed paste,
+/// regardless of whether any of those were changed.
+/// It's a safety valve to call at shutdown or from a panic hook, see [`install_cleanup_hook`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{reset_terminal, slide, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// let result = slide!(TypewriterPrint("Hello, world!", Duration::from_millis(25)));
+///
+/// reset_terminal().expect("the terminal should be reset");
+/// result.expect("the slide should appear");
+/// ```
+///
+/// # Errors
+///
+/// Every step is attempted even if an earlier one fails, and the first error is returned.
+pub fn reset_terminal() -> io::Result<()> {
+    let raw_mode = disable_raw_mode();
+    let commands = execute!(
+        output(),
+        ResetColor,
+        SetAttribute(Attribute::Reset),
+        Show,
+        LeaveAlternateScreen,
+        EnableLineWrap,
+        DisableMouseCapture,
+        DisableFocusChange,
+        DisableBracketedPaste,
+    );
+
+    raw_mode.and(commands)
+}
+
+/// Installs a panic hook that restores the terminal with [`reset_terminal`] before the panic message is printed,
+/// so a slide that panics doesn't leave the terminal in raw mode with a hidden cursor.
+///
+/// The hook that was installed before, whether it's the default one or your own, still runs afterward,
+/// so the panic message is printed as usual.
+/// The screen isn't cleared, so whatever the slide printed stays visible above the message.
+/// Calling this more than once does nothing, so the terminal isn't reset more than once per panic.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{install_cleanup_hook, slide, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// install_cleanup_hook();
+///
+/// slide!(TypewriterPrint("Hello, world!", Duration::from_millis(25)))
+///     .expect("the terminal should be reset before this message if it fails");
+/// ```
+pub fn install_cleanup_hook() {
+    static INSTALLED: Once = Once::new();
+
+    INSTALLED.call_once(|| {
+        let previous = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            let _ = reset_terminal();
+
+            previous(info);
+        }));
+    });
+}
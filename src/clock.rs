@@ -0,0 +1,332 @@
+//! The time elapsed since a [`Presentation`](crate::Presentation) started, and how long its slides are shown for.
+
+use crate::output::{self, output};
+use crate::runner;
+use crossterm::cursor::{MoveTo, RestorePosition, SavePosition};
+use crossterm::style::{Print, Stylize};
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::Command;
+use std::cell::Cell;
+use std::fmt::{self, Display, Formatter};
+use std::io::Write as _;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static COUNTDOWN: Cell<Option<Running>> = const { Cell::new(None) };
+    static COUNTDOWN_HOLDS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// How long a slide keeps waiting after it's been shown for the maximum slide duration, before it advances.
+const GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+/// A corner of the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Corner {
+    /// The top left corner.
+    TopLeft,
+    /// The top right corner.
+    #[default]
+    TopRight,
+    /// The bottom left corner.
+    BottomLeft,
+    /// The bottom right corner.
+    BottomRight,
+}
+
+impl Corner {
+    /// Returns the column and row that text `width` columns wide starts at to be in this corner of the terminal.
+    pub(crate) fn position(self, width: u16) -> (u16, u16) {
+        let (columns, rows) = output::terminal_size().unwrap_or((80, 24));
+        let column = match self {
+            Self::TopLeft | Self::BottomLeft => 0,
+            Self::TopRight | Self::BottomRight => columns.saturating_sub(width),
+        };
+        let row = match self {
+            Self::TopLeft | Self::TopRight => 0,
+            Self::BottomLeft | Self::BottomRight => rows.saturating_sub(1),
+        };
+
+        (column, row)
+    }
+}
+
+/// A command that prints the time elapsed since the running [`Presentation`](crate::Presentation) started,
+/// as minutes and seconds like `07:42`.
+///
+/// Minutes keep counting past an hour, so a long talk shows `75:03`, for example.
+/// Outside of a presentation, `00:00` is printed.
+/// To keep the time in a corner of every slide, and updated while waiting for interactions,
+/// use [`Presentation::elapsed_time`](crate::Presentation::elapsed_time) instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, ElapsedTime, Presentation, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// Presentation::new()
+///     .slide(|| {
+///         slide!(
+///             TypewriterPrint("Time check: ", Duration::from_millis(25)),
+///             ElapsedTime,
+///         )
+///     })
+///     .run()
+///     .expect("the presentation should run");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`ElapsedTime`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElapsedTime;
+
+impl Command for ElapsedTime {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "{}", MinutesSeconds(runner::elapsed()))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute ElapsedTime command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// A command that shows the time left before the given duration is up in a corner of the terminal,
+/// as minutes and seconds like `04:59`, keeping it updated for the rest of the slide.
+///
+/// This is useful for rehearsing a segment of a talk to a time limit.
+/// The time left is updated once a second while text is typed and while waiting, including for interactions,
+/// and once it's up, `00:00` is shown highlighted until the slide is over.
+/// Within a running [`Presentation`](crate::Presentation), the time counts from when the slide was first shown,
+/// so it isn't reset when the slide is shown again, like after the help overlay is closed.
+/// For the time elapsed since the presentation started instead, see [`ElapsedTime`].
+/// Nothing is shown while rendering headlessly.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, Corner, Countdown, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// slide!(
+///     Countdown(Duration::from_secs(5 * 60), Corner::BottomRight),
+///     TypewriterPrint("Live demo time!", Duration::from_millis(25)),
+/// )
+/// .expect("the demo slide should appear with five minutes on the clock");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`Countdown`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Countdown(pub Duration, pub Corner);
+
+/// A countdown started by [`Countdown`], and the time it last showed.
+#[derive(Debug, Clone, Copy)]
+struct Running {
+    deadline: Instant,
+    corner: Corner,
+    shown: Option<u64>,
+}
+
+impl Command for Countdown {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if output::is_headless() {
+            return Ok(());
+        }
+
+        let started = runner::slide_started().unwrap_or_else(Instant::now);
+
+        COUNTDOWN.with(|countdown| {
+            countdown.set(Some(Running {
+                deadline: started + self.0,
+                corner: self.1,
+                shown: None,
+            }));
+        });
+        draw_countdown(f)?;
+
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute Countdown command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Draws the time left on the countdown started by [`Countdown`] if it's changed since it was last drawn,
+/// leaving the cursor where it was.
+///
+/// Nothing is drawn while the countdown is held, see [`hold_countdown`], but it's drawn the next time after that.
+/// Returns how long until the time left changes, or [`None`] if there's no countdown or its time is up.
+pub(crate) fn draw_countdown(f: &mut impl fmt::Write) -> Result<Option<Duration>, fmt::Error> {
+    let Some(mut running) = COUNTDOWN.with(Cell::get) else {
+        return Ok(None);
+    };
+    let remaining = running.deadline.saturating_duration_since(Instant::now());
+    let seconds = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    let held = COUNTDOWN_HOLDS.with(Cell::get) > 0;
+
+    if running.shown != Some(seconds) && !held {
+        let time = MinutesSeconds(Duration::from_secs(seconds)).to_string();
+        let (column, row) = running.corner.position(time.chars().count() as u16);
+
+        SavePosition.write_ansi(f)?;
+        MoveTo(column, row).write_ansi(f)?;
+
+        if seconds == 0 {
+            Print(time.reverse()).write_ansi(f)?;
+        } else {
+            Print(time).write_ansi(f)?;
+        }
+
+        RestorePosition.write_ansi(f)?;
+        running.shown = Some(seconds);
+        COUNTDOWN.with(|countdown| countdown.set(Some(running)));
+    }
+
+    match (seconds, remaining.subsec_nanos()) {
+        (0, _) => Ok(None),
+        (_, 0) => Ok(Some(Duration::from_secs(1))),
+        (_, nanos) => Ok(Some(Duration::from_nanos(nanos.into()))),
+    }
+}
+
+/// Draws the countdown started by [`Countdown`] to [`output`] like [`draw_countdown`], flushing it if it's drawn.
+///
+/// Errors are kept to be returned by the output, see [`output::fail`].
+pub(crate) fn update_countdown() -> Option<Duration> {
+    let mut drawn = String::new();
+    let until_change = draw_countdown(&mut drawn).ok().flatten();
+
+    if !drawn.is_empty() {
+        if let Err(error) = output().write_all(drawn.as_bytes()) {
+            output::fail(error);
+        }
+
+        output::flush();
+    }
+
+    until_change
+}
+
+/// Sleeps until `deadline`, keeping the countdown started by [`Countdown`] updated meanwhile if there is one.
+pub(crate) fn sleep_until(deadline: Instant, sleep: impl Fn(Duration)) {
+    loop {
+        let until_change = update_countdown();
+        let remaining = deadline.saturating_duration_since(Instant::now());
+
+        match until_change {
+            Some(until_change) if until_change < remaining => sleep(until_change),
+            _ => return sleep(remaining),
+        }
+    }
+}
+
+/// Keeps the countdown from being drawn until it's dropped, see [`hold_countdown`].
+pub(crate) struct HeldCountdown;
+
+impl Drop for HeldCountdown {
+    fn drop(&mut self) {
+        COUNTDOWN_HOLDS.with(|holds| holds.set(holds.get() - 1));
+    }
+}
+
+/// Keeps the countdown started by [`Countdown`] from being drawn until the returned guard is dropped.
+///
+/// Terminals only have one saved cursor position, which drawing the countdown overwrites,
+/// so commands that restore a saved position after a delay hold the countdown meanwhile.
+pub(crate) fn hold_countdown() -> HeldCountdown {
+    COUNTDOWN_HOLDS.with(|holds| holds.set(holds.get() + 1));
+
+    HeldCountdown
+}
+
+/// Stops the countdown started by [`Countdown`] when it's dropped, at the end of a slide.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct SlideEnd;
+
+impl Drop for SlideEnd {
+    fn drop(&mut self) {
+        COUNTDOWN.with(|countdown| countdown.set(None));
+    }
+}
+
+/// Formats a duration as minutes and seconds.
+pub(crate) struct MinutesSeconds(pub(crate) Duration);
+
+impl Display for MinutesSeconds {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let seconds = self.0.as_secs();
+
+        write!(f, "{:02}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+/// Draws the elapsed time in `corner`, dimmed, leaving the cursor where it was.
+pub(crate) fn draw(f: &mut impl fmt::Write, corner: Corner) -> fmt::Result {
+    let time = MinutesSeconds(runner::elapsed()).to_string();
+    let (column, row) = corner.position(time.chars().count() as u16);
+
+    SavePosition.write_ansi(f)?;
+    MoveTo(column, row).write_ansi(f)?;
+    Print(time.dim()).write_ansi(f)?;
+    RestorePosition.write_ansi(f)
+}
+
+/// Draws a warning on the bottom row that the slide advances in `remaining`, leaving the cursor where it was.
+pub(crate) fn draw_overtime_warning(f: &mut impl fmt::Write, remaining: Duration) -> fmt::Result {
+    let seconds = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    let (_, rows) = output::terminal_size().unwrap_or((80, 24));
+
+    SavePosition.write_ansi(f)?;
+    MoveTo(0, rows.saturating_sub(1)).write_ansi(f)?;
+    Clear(ClearType::CurrentLine).write_ansi(f)?;
+    Print(format!(" Over time, advancing in {seconds}s ").reverse()).write_ansi(f)?;
+    RestorePosition.write_ansi(f)
+}
+
+/// Returns how long to wait before the maximum slide duration countdown changes,
+/// or [`None`] once it's over and the slide should advance.
+///
+/// The warning is drawn while the countdown is going.
+pub(crate) fn overtime(
+    f: &mut impl fmt::Write,
+    deadline: Instant,
+) -> Result<Option<Duration>, fmt::Error> {
+    let now = Instant::now();
+
+    if now < deadline {
+        return Ok(Some(deadline - now));
+    }
+
+    let remaining = (deadline + GRACE_PERIOD).saturating_duration_since(now);
+
+    if remaining.is_zero() {
+        return Ok(None);
+    }
+
+    draw_overtime_warning(f, remaining)?;
+
+    match remaining.subsec_nanos() {
+        0 => Ok(Some(Duration::from_secs(1))),
+        nanos => Ok(Some(Duration::from_nanos(nanos.into()))),
+    }
+}
@@ -0,0 +1,158 @@
+//! Source code highlighted with [syntect](https://docs.rs/syntect/).
+
+use crate::typing::typewrite;
+use crate::Typing;
+use crossterm::style::{Attribute, Color, SetAttribute, SetForegroundColor};
+use crossterm::Command;
+use std::fmt::{self, Display};
+use std::sync::OnceLock;
+use std::time::Duration;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{FontStyle, Theme, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+/// The bundled theme that code is highlighted with.
+const THEME: &str = "base16-ocean.dark";
+
+/// A command that types source code, highlighting its syntax.
+///
+/// The language is looked up by name or file extension, like `"Rust"` or `"rs"`,
+/// among the languages bundled with syntect, and code in a language that isn't found is typed without highlighting.
+/// Every character is typed in the color of the token it belongs to, so the colors appear as the code is typed,
+/// and a delay of [`Duration::ZERO`] prints the whole block instantly, like any other typewriter command.
+/// Only foreground colors, bold, italics and underlines are used, so code blends into the terminal's background.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, CodeBlock};
+/// use std::time::Duration;
+///
+/// slide!(CodeBlock(
+///     "fn main() {\n    println!(\"Hello, world!\");\n}",
+///     "rs",
+///     Duration::from_millis(25),
+/// ))
+/// .expect("the code should be typed with its syntax highlighted");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`CodeBlock`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeBlock<T: Display, L: Display, O: Into<Typing> + Copy = Duration>(
+    pub T,
+    pub L,
+    pub O,
+);
+
+impl<T: Display, L: Display, O: Into<Typing> + Copy> Command for CodeBlock<T, L, O> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        typewrite(
+            f,
+            &highlight(&self.0.to_string(), &self.1.to_string())?,
+            None,
+            self.2.into(),
+        )
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute CodeBlock command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the bundled syntaxes and theme, loading them the first time they're needed.
+fn highlighting() -> &'static (SyntaxSet, Theme) {
+    static HIGHLIGHTING: OnceLock<(SyntaxSet, Theme)> = OnceLock::new();
+
+    HIGHLIGHTING.get_or_init(|| {
+        let theme = ThemeSet::load_defaults()
+            .themes
+            .remove(THEME)
+            .expect("the bundled theme should exist");
+
+        (SyntaxSet::load_defaults_newlines(), theme)
+    })
+}
+
+/// Returns `source` with the escape sequences of the colors and styles of its tokens before each of them.
+fn highlight(source: &str, language: &str) -> Result<String, fmt::Error> {
+    let (syntaxes, theme) = highlighting();
+    let syntax = syntaxes
+        .find_syntax_by_token(language)
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    let mut highlighted = String::with_capacity(source.len());
+    let mut previous = None;
+
+    for line in LinesWithEndings::from(source) {
+        // Erroring would make crossterm panic, since the writer itself didn't fail,
+        // so a line that can't be highlighted is typed as it is instead.
+        let Ok(tokens) = highlighter.highlight_line(line, syntaxes) else {
+            if previous.take().is_some() {
+                reset_font_style(&mut highlighted)?;
+                SetForegroundColor(Color::Reset).write_ansi(&mut highlighted)?;
+            }
+
+            highlighted.push_str(line);
+            continue;
+        };
+
+        for (style, token) in tokens {
+            // Adjacent tokens often look the same, like punctuation and the whitespace around it.
+            if previous.replace(style) == Some(style) {
+                highlighted.push_str(token);
+                continue;
+            }
+
+            let color = style.foreground;
+
+            reset_font_style(&mut highlighted)?;
+            SetForegroundColor(Color::Rgb {
+                r: color.r,
+                g: color.g,
+                b: color.b,
+            })
+            .write_ansi(&mut highlighted)?;
+
+            for (font_style, attribute) in [
+                (FontStyle::BOLD, Attribute::Bold),
+                (FontStyle::ITALIC, Attribute::Italic),
+                (FontStyle::UNDERLINE, Attribute::Underlined),
+            ] {
+                if style.font_style.contains(font_style) {
+                    SetAttribute(attribute).write_ansi(&mut highlighted)?;
+                }
+            }
+
+            highlighted.push_str(token);
+        }
+    }
+
+    reset_font_style(&mut highlighted)?;
+    SetForegroundColor(Color::Reset).write_ansi(&mut highlighted)?;
+
+    Ok(highlighted)
+}
+
+/// Turns off bold, italics and underlines, without resetting the background like [`Attribute::Reset`] would.
+fn reset_font_style(f: &mut impl fmt::Write) -> fmt::Result {
+    for attribute in [
+        Attribute::NormalIntensity,
+        Attribute::NoItalic,
+        Attribute::NoUnderline,
+    ] {
+        SetAttribute(attribute).write_ansi(f)?;
+    }
+
+    Ok(())
+}
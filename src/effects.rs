@@ -0,0 +1,599 @@
+//! Typewriter effects beyond printing text in place.
+
+use crate::markers::{self, Segment};
+use crate::typing::{pause, typewrite, typewrite_segments};
+use crate::{clock, layout, output, random, text, Typing, WaitForInteraction};
+use crossterm::cursor::{
+    MoveDown, MoveLeft, MoveTo, MoveToColumn, MoveToNextLine, MoveToPreviousLine, MoveUp,
+    RestorePosition, SavePosition,
+};
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::Command;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::f32::consts::TAU;
+use std::fmt::{self, Display};
+use std::time::Duration;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A command that types each piece of text at its own position, in order.
+///
+/// Each step is a `(column, row)` position, the text to type there,
+/// and the delay between its characters.
+/// This is useful for annotations that pop up around a diagram, for example.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, Sequence};
+/// use std::time::Duration;
+///
+/// slide!(Sequence(vec![
+///     ((2, 1), "<- the input", Duration::from_millis(25)),
+///     ((30, 6), "the output ->", Duration::from_millis(25)),
+/// ]))
+/// .expect("each annotation should be typed at its position");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`Sequence`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence<T: Display>(pub Vec<((u16, u16), T, Duration)>);
+
+impl<T: Display> Command for Sequence<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        for ((column, row), text, delay) in &self.0 {
+            MoveTo(*column, *row).write_ansi(f)?;
+            typewrite(f, &text.to_string(), None, Typing::new(*delay))?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute Sequence command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// A command that types lines into a region of a fixed height, scrolling it like a log being tailed.
+///
+/// Once the region is full, every new line pushes the oldest visible one out of the top of the region,
+/// without scrolling the rest of the terminal.
+/// Lines are cut off at the width of the terminal, or of the content width if there is one,
+/// so each of them takes up exactly one row.
+/// Only the first line of each piece of text is typed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, ScrollingLog};
+/// use std::time::Duration;
+///
+/// slide!(ScrollingLog(
+///     (1..=20).map(|n| format!("[INFO] handled request #{n}")).collect(),
+///     5,
+///     Duration::from_millis(5),
+/// ))
+/// .expect("the log should scroll within five rows");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`ScrollingLog`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScrollingLog<T: Display, O: Into<Typing> + Copy = Duration>(pub Vec<T>, pub u16, pub O);
+
+impl<T: Display, O: Into<Typing> + Copy> Command for ScrollingLog<T, O> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let height = self.1.max(1) as usize;
+        let (_, width) = layout::content_area();
+        let mut visible: VecDeque<Vec<Segment>> = VecDeque::with_capacity(height);
+
+        for line in &self.0 {
+            let line = line.to_string();
+            let segments = truncate(
+                text::split_escapes(markers::parse(line.lines().next().unwrap_or_default())),
+                width as usize,
+            );
+
+            if visible.len() == height {
+                visible.pop_front();
+                MoveToPreviousLine(height as u16).write_ansi(f)?;
+
+                // Lines are redrawn as they were printed, in the same columns, without pausing again.
+                for visible_line in &visible {
+                    Clear(ClearType::UntilNewLine).write_ansi(f)?;
+
+                    for segment in layout::arrange(visible_line.clone()) {
+                        match segment {
+                            Segment::Text(content) | Segment::Raw(content) => {
+                                f.write_str(&content)?
+                            }
+                            Segment::Pause(_) => {}
+                        }
+                    }
+
+                    text::write_newline(f)?;
+                }
+
+                Clear(ClearType::UntilNewLine).write_ansi(f)?;
+            }
+
+            typewrite_segments(f, segments.clone(), |_, _| None, self.2.into(), &mut |_| {})?;
+            text::write_newline(f)?;
+            visible.push_back(segments);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute ScrollingLog command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the text that typing `text` prints, without its pause markers, escape sequences and carriage returns.
+fn printed(text: &str) -> String {
+    text::split_escapes(markers::parse(&text::normalize_newlines(text)))
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Text(content) => Some(content.replace('\r', "")),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Clears what typing text that printed `printed` from `column` took up,
+/// which is the rest of the cursor's row and every other row the text took up.
+///
+/// The cursor ends up at the start of the last row that was cleared.
+fn clear_typed(f: &mut impl fmt::Write, printed: &str, column: u16) -> fmt::Result {
+    Clear(ClearType::UntilNewLine).write_ansi(f)?;
+
+    for _ in 1..layout::rows_from(printed, column) {
+        MoveToNextLine(1).write_ansi(f)?;
+        Clear(ClearType::CurrentLine).write_ansi(f)?;
+    }
+
+    Ok(())
+}
+
+/// Cuts the text of `segments` off once it would take up more than `width` columns, keeping the other segments.
+fn truncate(segments: Vec<Segment>, width: usize) -> Vec<Segment> {
+    let mut columns = 0;
+
+    segments
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Text(content) => Segment::Text(
+                content
+                    .graphemes(true)
+                    .take_while(|grapheme| {
+                        columns += layout::width(grapheme);
+                        columns <= width
+                    })
+                    .collect(),
+            ),
+            segment => segment,
+        })
+        .collect()
+}
+
+/// The characters that [`DecodePrint`] cycles through before settling on the real one.
+const DECODE_GLYPHS: &[char] = &[
+    '!', '#', '$', '%', '&', '*', '+', '<', '=', '>', '?', '@', '0', '1', '2', '3', '4', '5', '6',
+    '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'X', 'Y', 'Z', '/', '\\', '|', '~', '^',
+];
+
+/// A command that reveals text like it's being decoded,
+/// cycling each character through random glyphs before it settles on the real one.
+///
+/// Characters are decoded from left to right,
+/// and each one shows `iterations` random glyphs for the given delay each before locking in.
+/// Whitespace is printed as-is.
+/// The glyphs come from a generator that can be seeded with [`seed_random`](crate::seed_random).
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, DecodePrint};
+/// use std::time::Duration;
+///
+/// slide!(DecodePrint("ACCESS GRANTED", Duration::from_millis(15), 6))
+///     .expect("\"ACCESS GRANTED\" should be decoded");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`DecodePrint`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodePrint<T: Display>(pub T, pub Duration, pub u16);
+
+impl<T: Display> Command for DecodePrint<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        for char in text::normalize_newlines(&self.0.to_string()).chars() {
+            if char == '\n' {
+                text::write_newline(f)?;
+                continue;
+            }
+
+            if !char.is_whitespace() {
+                for _ in 0..self.2 {
+                    f.write_char(DECODE_GLYPHS[random::below(DECODE_GLYPHS.len())])?;
+                    pause(self.1);
+                    MoveLeft(1).write_ansi(f)?;
+                }
+            }
+
+            f.write_char(char)?;
+            pause(self.1);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute DecodePrint command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// A command that types text, shows it for a while, then clears it again, like a toast notification.
+///
+/// The text is typed with the slide's typing options (see [`Typing::inherit`]),
+/// and once it disappears the cursor is back where the text began,
+/// so a toast can be layered over a slide without advancing it or waiting for an interaction.
+/// The part of the first row after where the text began is cleared, as is every other row the text took up.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, Toast, TypewriterPrint, WaitForInteraction};
+/// use std::time::Duration;
+///
+/// slide!(
+///     TypewriterPrint("Here's the first point.\n", Duration::from_millis(25)),
+///     Toast("(press space to continue)", Duration::from_secs(2)),
+///     WaitForInteraction,
+/// )
+/// .expect("the hint should appear, then disappear");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`Toast`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Toast<T: Display>(pub T, pub Duration);
+
+impl<T: Display> Command for Toast<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let text = self.0.to_string();
+        let column = layout::current_column();
+        let _held = clock::hold_countdown();
+
+        SavePosition.write_ansi(f)?;
+        typewrite(f, &text, None, Typing::inherit())?;
+        pause(self.1);
+        RestorePosition.write_ansi(f)?;
+        clear_typed(f, &printed(&text), column)?;
+
+        RestorePosition.write_ansi(f)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute Toast command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// The character that [`RevealMasked`] hides every character behind before revealing it.
+const MASK: char = '•';
+
+/// A command that prints text masked, like a password, then reveals its real characters one at a time.
+///
+/// Every character except whitespace is first hidden behind a `•` for each column it takes up,
+/// then the cursor goes back to where the text began and overwrites each mask with the real character,
+/// waiting the given delay after each one.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, RevealMasked};
+/// use std::time::Duration;
+///
+/// slide!(RevealMasked("hunter2", Duration::from_millis(150)))
+///     .expect("\"hunter2\" should be revealed from behind its mask");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`RevealMasked`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RevealMasked<T: Display>(pub T, pub Duration);
+
+impl<T: Display> Command for RevealMasked<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let text = text::normalize_newlines(&self.0.to_string()).into_owned();
+        let _held = clock::hold_countdown();
+
+        SavePosition.write_ansi(f)?;
+
+        for char in text.chars() {
+            if char == '\n' {
+                text::write_newline(f)?;
+            } else if char.is_whitespace() {
+                f.write_char(char)?;
+            } else {
+                for _ in 0..layout::width(char.encode_utf8(&mut [0; 4])) {
+                    f.write_char(MASK)?;
+                }
+            }
+        }
+
+        pause(self.1);
+        RestorePosition.write_ansi(f)?;
+
+        for char in text.chars() {
+            if char == '\n' {
+                text::write_newline(f)?;
+            } else {
+                f.write_char(char)?;
+
+                if !char.is_whitespace() {
+                    pause(self.1);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute RevealMasked command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// How many characters [`WavePrint`] types per period of its wave.
+const WAVE_PERIOD: f32 = 12.0;
+
+/// A command that types text along a sine wave, moving each character up or down from the middle row.
+///
+/// The wave reaches `amplitude` rows above and below its middle row,
+/// so the text takes up twice that plus one rows, starting at the cursor's row.
+/// The text is typed on a single wave, so line breaks are typed as spaces.
+/// Afterward, the cursor is on the middle row, just after the last character.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, WavePrint};
+/// use std::time::Duration;
+///
+/// slide!(WavePrint("Wheeeeeeeeeeee!", 2, Duration::from_millis(50)))
+///     .expect("\"Wheeeeeeeeeeee!\" should be typed along a wave");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`WavePrint`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WavePrint<T: Display>(pub T, pub u16, pub Duration);
+
+impl<T: Display> Command for WavePrint<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let amplitude = self.1;
+        let column = layout::current_column();
+        let mut row = 0;
+
+        // Makes room for the whole wave first, in case the cursor is near the bottom of the terminal.
+        if amplitude > 0 {
+            for _ in 0..amplitude * 2 {
+                text::write_newline(f)?;
+            }
+
+            MoveUp(amplitude * 2).write_ansi(f)?;
+            MoveToColumn(column).write_ansi(f)?;
+        }
+
+        for (index, char) in text::normalize_newlines(&self.0.to_string())
+            .chars()
+            .enumerate()
+        {
+            let wave = (index as f32 * TAU / WAVE_PERIOD).sin();
+            let target = (f32::from(amplitude) * (1.0 - wave)).round() as u16;
+
+            move_rows(f, row, target)?;
+            row = target;
+            f.write_char(if char == '\n' { ' ' } else { char })?;
+
+            if !char.is_whitespace() {
+                pause(self.2);
+            }
+        }
+
+        move_rows(f, row, amplitude)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute WavePrint command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Moves the cursor from row `from` to row `to`, staying in the same column.
+fn move_rows(f: &mut impl fmt::Write, from: u16, to: u16) -> fmt::Result {
+    match to.cmp(&from) {
+        Ordering::Greater => MoveDown(to - from).write_ansi(f),
+        Ordering::Less => MoveUp(from - to).write_ansi(f),
+        Ordering::Equal => Ok(()),
+    }
+}
+
+/// A command that types a "before" block, waits for an interaction, then replaces it with an "after" block.
+///
+/// Only the region the "before" block took up is cleared, so whatever is around it stays where it is,
+/// and the "after" block is typed where the "before" block began.
+/// Both blocks are typed with the slide's typing options, see [`Typing::inherit`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, BeforeAfter};
+///
+/// slide!(BeforeAfter(
+///     "for i in 0..v.len() {\n    total += v[i];\n}",
+///     "let total: i32 = v.iter().sum();",
+/// ))
+/// .expect("the loop should be replaced by the iterator after an interaction");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`BeforeAfter`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeforeAfter<B: Display, A: Display>(pub B, pub A);
+
+impl<B: Display, A: Display> Command for BeforeAfter<B, A> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let before = self.0.to_string();
+        let printed = printed(&before);
+        let column = layout::current_column();
+
+        typewrite(f, &before, None, Typing::inherit())?;
+        WaitForInteraction.write_ansi(f)?;
+
+        // The saved position can't be relied on across the wait, since the elapsed time is drawn with it.
+        let rows = layout::rows_from(&printed, column);
+
+        if rows > 1 {
+            MoveToPreviousLine(rows - 1).write_ansi(f)?;
+        }
+
+        MoveToColumn(column).write_ansi(f)?;
+        SavePosition.write_ansi(f)?;
+        clear_typed(f, &printed, column)?;
+        RestorePosition.write_ansi(f)?;
+        typewrite(f, &self.1.to_string(), None, Typing::inherit())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute BeforeAfter command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// A command that erases the `n` characters before the cursor one at a time, like backspacing over a typo.
+///
+/// Each character is erased by moving the cursor left and clearing the rest of the row, with the given delay after each.
+/// When the cursor reaches the first column, it moves to the last column of the row above,
+/// so text the terminal wrapped onto several rows is erased across them.
+/// Each character is assumed to take up one column, so a wide character counts as two,
+/// and a line break counts as the rest of the row before it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, TypewriterErase, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// slide!(
+///     TypewriterPrint("Hello, wrold", Duration::from_millis(50)),
+///     TypewriterErase(4, Duration::from_millis(80)),
+///     TypewriterPrint("orld!", Duration::from_millis(50)),
+/// )
+/// .expect("the typo should be erased and fixed");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`TypewriterErase`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypewriterErase(pub usize, pub Duration);
+
+impl Command for TypewriterErase {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let columns = output::terminal_size().map_or(80, |(columns, _)| columns.max(1));
+        let mut column = layout::current_column();
+
+        for _ in 0..self.0 {
+            if column == 0 {
+                MoveUp(1).write_ansi(f)?;
+                MoveToColumn(columns - 1).write_ansi(f)?;
+                column = columns - 1;
+            } else {
+                MoveLeft(1).write_ansi(f)?;
+                column -= 1;
+            }
+
+            Clear(ClearType::UntilNewLine).write_ansi(f)?;
+            pause(self.1);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute TypewriterErase command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
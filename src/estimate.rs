@@ -0,0 +1,50 @@
+//! Estimating how long slides take, by adding up their delays instead of sleeping through them.
+
+use std::cell::Cell;
+use std::time::Duration;
+
+thread_local! {
+    static ESTIMATE: Cell<Option<Estimate>> = const { Cell::new(None) };
+}
+
+/// How long a slide spends on its delays, and how many interactions it waits for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct Estimate {
+    /// The total of the delays of the slide, like those between typed characters.
+    pub(crate) delays: Duration,
+    /// The number of times the slide waits for an interaction.
+    pub(crate) interactions: usize,
+}
+
+/// Runs `render`, which should render headlessly, and returns the delays and interactions it would've waited for.
+pub(crate) fn measure(render: impl FnOnce()) -> Estimate {
+    let previous = ESTIMATE.with(|estimate| estimate.replace(Some(Estimate::default())));
+
+    render();
+
+    ESTIMATE
+        .with(|estimate| estimate.replace(previous))
+        .unwrap_or_default()
+}
+
+/// Counts a delay towards the estimate being measured, if there is one.
+pub(crate) fn add_delay(delay: Duration) {
+    #[cfg(feature = "async")]
+    crate::playback::mark_delay(delay);
+
+    update(|estimate| estimate.delays = estimate.delays.saturating_add(delay));
+}
+
+/// Counts an interaction towards the estimate being measured, if there is one.
+pub(crate) fn add_interaction() {
+    update(|estimate| estimate.interactions += 1);
+}
+
+fn update(f: impl FnOnce(&mut Estimate)) {
+    ESTIMATE.with(|estimate| {
+        if let Some(mut current) = estimate.get() {
+            f(&mut current);
+            estimate.set(Some(current));
+        }
+    });
+}
@@ -0,0 +1,113 @@
+//! Text art made with [figlet](http://www.figlet.org/) fonts, which are registered by name.
+
+use crate::layout::with_content_width;
+use crate::typing::typewrite;
+use crate::Typing;
+use crossterm::Command;
+use figlet_rs::FIGfont;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::io;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// The name of the font that's available without being registered.
+const STANDARD: &str = "standard";
+
+static FONTS: RwLock<BTreeMap<String, Arc<FIGfont>>> = RwLock::new(BTreeMap::new());
+
+/// Parses the contents of a figlet font file and registers it under `name`,
+/// so [`FigletPrint`] can use it without parsing it again.
+///
+/// A font registered under an existing name replaces the previous one.
+/// The `standard` font is bundled, so it can be used without being registered.
+///
+/// # Errors
+///
+/// Returns an error if `content` isn't a valid figlet font.
+pub fn register_figlet_font(name: impl Into<String>, content: &str) -> io::Result<()> {
+    let font = FIGfont::from_content(content)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+    FONTS
+        .write()
+        .expect("figlet fonts lock should not be poisoned")
+        .insert(name.into(), Arc::new(font));
+
+    Ok(())
+}
+
+/// Returns the font registered under `name`, parsing the bundled `standard` font the first time it's needed.
+fn font(name: &str) -> Option<Arc<FIGfont>> {
+    if let Some(font) = FONTS
+        .read()
+        .expect("figlet fonts lock should not be poisoned")
+        .get(name)
+    {
+        return Some(Arc::clone(font));
+    }
+
+    if name != STANDARD {
+        return None;
+    }
+
+    let font = Arc::new(FIGfont::standard().expect("the standard figlet font should be parsed"));
+
+    FONTS
+        .write()
+        .expect("figlet fonts lock should not be poisoned")
+        .insert(STANDARD.to_string(), Arc::clone(&font));
+
+    Some(font)
+}
+
+/// A command that types text as art made with a figlet font, which is looked up by name.
+///
+/// Fonts are registered with [`register_figlet_font`], except for `standard`, which is bundled.
+/// The art is never wrapped to the content width of the running [`Presentation`](crate::Presentation),
+/// since that would break it apart.
+/// If no font is registered under the name, or the font doesn't have some of the characters of the text,
+/// the text is typed as-is instead, so a typo in the name doesn't break a live slide.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, FigletPrint};
+/// use std::time::Duration;
+///
+/// slide!(FigletPrint("clp", "standard", Duration::from_millis(2)))
+///     .expect("\"clp\" should be typed as text art");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`FigletPrint`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FigletPrint<T: Display, O: Into<Typing> + Copy = Duration>(
+    pub T,
+    pub &'static str,
+    pub O,
+);
+
+impl<T: Display, O: Into<Typing> + Copy> Command for FigletPrint<T, O> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let text = self.0.to_string();
+        let art = font(self.1)
+            .and_then(|font| font.convert(&text).map(|figure| figure.to_string()))
+            .unwrap_or(text);
+
+        with_content_width(None, || typewrite(f, &art, None, self.2.into()))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute FigletPrint command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
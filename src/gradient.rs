@@ -0,0 +1,106 @@
+//! Text colored along a gradient.
+
+use crate::typing::typewrite_styled;
+use crate::Typing;
+use crossterm::style::{Color, ContentStyle};
+use crossterm::Command;
+use std::env;
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+/// A command that types text with each character colored along a gradient,
+/// from the first RGB color at the first character to the second at the last.
+///
+/// The gradient goes left to right through the printed characters,
+/// so pause markers and line breaks don't take up any of it.
+/// If the terminal doesn't advertise support for 24-bit color through the `COLORTERM` environment variable,
+/// each color is snapped to the nearest color of the 256-color palette instead.
+/// The delay can be any [`Typing`] options, like in [`TypewriterPrint`](crate::TypewriterPrint),
+/// and [`Duration::ZERO`](std::time::Duration::ZERO) prints the whole gradient at once.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, GradientPrint};
+/// use std::time::Duration;
+///
+/// slide!(GradientPrint(
+///     "Command Line Presentations",
+///     (255, 95, 109),
+///     (255, 195, 113),
+///     Duration::from_millis(25),
+/// ))
+/// .expect("the title should be typed from pink to orange");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`GradientPrint`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GradientPrint<T: Display, O: Into<Typing> + Copy = Duration>(
+    pub T,
+    pub (u8, u8, u8),
+    pub (u8, u8, u8),
+    pub O,
+);
+
+impl<T: Display, O: Into<Typing> + Copy> Command for GradientPrint<T, O> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let truecolor = env::var("COLORTERM")
+            .is_ok_and(|colorterm| colorterm == "truecolor" || colorterm == "24bit");
+        let style_at = |index: usize, total: usize| {
+            let progress = match total {
+                0 | 1 => 0.0,
+                total => index as f32 / (total - 1) as f32,
+            };
+            let (r, g, b) = interpolate(self.1, self.2, progress);
+
+            Some(ContentStyle {
+                foreground_color: Some(if truecolor {
+                    Color::Rgb { r, g, b }
+                } else {
+                    nearest_ansi(r, g, b)
+                }),
+                ..ContentStyle::new()
+            })
+        };
+
+        typewrite_styled(f, &self.0.to_string(), style_at, self.3.into(), &mut |_| {})
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute GradientPrint command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the color `progress` of the way from `start` to `end`.
+fn interpolate(start: (u8, u8, u8), end: (u8, u8, u8), progress: f32) -> (u8, u8, u8) {
+    let channel = |start: u8, end: u8| {
+        (f32::from(start) + (f32::from(end) - f32::from(start)) * progress).round() as u8
+    };
+
+    (
+        channel(start.0, end.0),
+        channel(start.1, end.1),
+        channel(start.2, end.2),
+    )
+}
+
+/// Returns the color of the 6×6×6 cube of the 256-color palette closest to the given RGB color.
+fn nearest_ansi(r: u8, g: u8, b: u8) -> Color {
+    let level = |channel: u8| match channel {
+        0..=47 => 0,
+        48..=114 => 1,
+        channel => (channel - 35) / 40,
+    };
+
+    Color::AnsiValue(16 + 36 * level(r) + 6 * level(g) + level(b))
+}
@@ -0,0 +1,233 @@
+//! Images shown with the inline image protocols of terminals that support them.
+
+use crate::typing::typewrite;
+use crate::{output, text, Typing};
+use crossterm::Command;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::RwLock;
+
+static IMAGE_PROTOCOL: RwLock<ImageProtocol> = RwLock::new(ImageProtocol::Auto);
+
+/// How [`SlideImage`] shows images, see [`set_image_protocol`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageProtocol {
+    /// The protocol is detected from the environment variables the terminal sets.
+    #[default]
+    Auto,
+    /// Kitty's graphics protocol, which is also supported by Ghostty and WezTerm, among others.
+    Kitty,
+    /// iTerm2's inline images protocol, which is also supported by WezTerm, among others.
+    Iterm,
+    /// Images aren't shown, and their text fallback is typed instead.
+    Text,
+}
+
+impl ImageProtocol {
+    /// Returns the protocol of the terminal, detected from the environment variables it sets,
+    /// or [`ImageProtocol::Text`] if it doesn't seem to support any.
+    pub fn detect() -> Self {
+        let var = |name| env::var(name).unwrap_or_default();
+
+        if env::var_os("KITTY_WINDOW_ID").is_some()
+            || var("TERM").contains("kitty")
+            || var("TERM").contains("ghostty")
+        {
+            Self::Kitty
+        } else if var("TERM_PROGRAM") == "iTerm.app"
+            || var("LC_TERMINAL") == "iTerm2"
+            || var("TERM_PROGRAM") == "WezTerm"
+        {
+            Self::Iterm
+        } else {
+            Self::Text
+        }
+    }
+}
+
+/// Sets how [`SlideImage`] shows images, which is detected from the environment by default.
+///
+/// Detection can't tell every terminal apart, especially over SSH or inside tmux,
+/// so this lets a presenter who knows their terminal choose its protocol.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{set_image_protocol, slide, ImageProtocol, SlideImage};
+///
+/// set_image_protocol(ImageProtocol::Kitty);
+///
+/// slide!(SlideImage::open("diagram.png").expect("the image should be read"))
+///     .expect("the image should be shown with Kitty's graphics protocol");
+/// ```
+pub fn set_image_protocol(protocol: ImageProtocol) {
+    *IMAGE_PROTOCOL
+        .write()
+        .expect("image protocol lock should not be poisoned") = protocol;
+}
+
+/// Returns how [`SlideImage`] shows images.
+pub fn image_protocol() -> ImageProtocol {
+    *IMAGE_PROTOCOL
+        .read()
+        .expect("image protocol lock should not be poisoned")
+}
+
+/// The number of base64 bytes sent in each chunk of Kitty's graphics protocol, which is the most it allows.
+const KITTY_CHUNK: usize = 4096;
+
+/// The bytes every PNG file starts with.
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// A command that shows an image inline, in terminals that support an inline image protocol.
+///
+/// Kitty's graphics protocol only supports PNG images, while iTerm2's supports most formats.
+/// In other terminals, while rendering headlessly, or if the image isn't in a format the protocol supports,
+/// the [`fallback`](SlideImage::fallback) is typed instead with the slide's typing options, if there is one,
+/// like ASCII art of the image.
+/// How the protocol is chosen can be changed with [`set_image_protocol`].
+/// Afterward, the cursor is at the start of the line below the image or its fallback.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, SlideImage};
+///
+/// let mut image = SlideImage::open("avatar.png").expect("the image should be read");
+///
+/// image.columns = Some(40);
+/// image.fallback = Some("[a picture of my avatar]".to_string());
+///
+/// slide!(image).expect("the image should be shown");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`SlideImage`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlideImage {
+    /// The contents of the image file.
+    pub data: Vec<u8>,
+    /// How many columns wide the image is shown, or [`None`] to show it at its own size.
+    pub columns: Option<u16>,
+    /// The text that's typed instead of the image if it can't be shown, or [`None`] to show nothing.
+    pub fallback: Option<String>,
+}
+
+impl SlideImage {
+    /// Creates an image from the contents of an image file, shown at its own size without a fallback.
+    pub fn new(data: impl Into<Vec<u8>>) -> Self {
+        Self {
+            data: data.into(),
+            columns: None,
+            fallback: None,
+        }
+    }
+
+    /// Creates an image from the image file at `path`, see [`SlideImage::new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(fs::read(path)?))
+    }
+
+    /// Returns the protocol the image is shown with, or [`ImageProtocol::Text`] if it can't be shown.
+    fn protocol(&self) -> ImageProtocol {
+        if output::is_headless() {
+            return ImageProtocol::Text;
+        }
+
+        match image_protocol() {
+            ImageProtocol::Auto => ImageProtocol::detect(),
+            protocol => protocol,
+        }
+    }
+}
+
+impl Command for SlideImage {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        match self.protocol() {
+            ImageProtocol::Kitty if self.data.starts_with(PNG_SIGNATURE) => {
+                let columns = self
+                    .columns
+                    .map_or_else(String::new, |columns| format!(",c={columns}"));
+                let encoded = base64(&self.data);
+                let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(KITTY_CHUNK).collect();
+
+                for (index, chunk) in chunks.iter().enumerate() {
+                    let keys = if index == 0 {
+                        format!("a=T,f=100{columns},")
+                    } else {
+                        String::new()
+                    };
+                    let more = u8::from(index + 1 < chunks.len());
+                    // Base64 is always ASCII, so every chunk is valid UTF-8.
+                    let chunk = std::str::from_utf8(chunk).map_err(|_| fmt::Error)?;
+
+                    write!(f, "\x1b_G{keys}m={more};{chunk}\x1b\\")?;
+                }
+            }
+            ImageProtocol::Iterm => {
+                let width = self
+                    .columns
+                    .map_or_else(String::new, |columns| format!(";width={columns}"));
+
+                write!(
+                    f,
+                    "\x1b]1337;File=inline=1;size={}{width}:{}\x07",
+                    self.data.len(),
+                    base64(&self.data),
+                )?;
+            }
+            _ => match &self.fallback {
+                Some(fallback) => typewrite(f, fallback, None, Typing::inherit())?,
+                None => return Ok(()),
+            },
+        }
+
+        text::write_newline(f)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute SlideImage command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Returns `data` encoded as base64, with padding, which is how both image protocols expect images.
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let group = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(ALPHABET[(group >> (18 - index * 6) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
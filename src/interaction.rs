@@ -0,0 +1,448 @@
+//! Interactions that return what the audience chose.
+
+use crate::output::{self, output};
+use crate::session::RawModeGuard;
+use crate::{layout, reset_terminal, TypewriterPrint};
+use crossterm::cursor::MoveLeft;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{Clear, ClearType};
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::io::{self, Write as _};
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use std::time::Duration;
+use std::{mem, ptr};
+
+static ECHO: AtomicBool = AtomicBool::new(false);
+static UNKNOWN_KEY: RwLock<UnknownKey> = RwLock::new(UnknownKey::Ignore);
+static ADVANCE_KEYS: RwLock<Cow<'static, [KeyCode]>> = RwLock::new(Cow::Borrowed(&[
+    KeyCode::Enter,
+    KeyCode::Right,
+    KeyCode::Char(' '),
+]));
+static QUIT_KEYS: RwLock<Cow<'static, [KeyCode]>> = RwLock::new(Cow::Borrowed(&[]));
+static QUIT_REQUESTED: AtomicBool = AtomicBool::new(false);
+static CTRL_C: RwLock<CtrlC> = RwLock::new(CtrlC::Interrupt);
+
+/// Sets the keys that are interactions for [`WaitForInteraction`](crate::WaitForInteraction),
+/// which are Enter, Right and Space by default.
+///
+/// This is useful for presentation clickers, many of which send Page Down and Page Up.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::crossterm::event::KeyCode;
+/// use clp::{set_advance_keys, slide, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// set_advance_keys(&[KeyCode::PageDown, KeyCode::Enter]);
+///
+/// slide!(TypewriterPrint("Click to continue.", Duration::from_millis(25)))
+///     .expect("the slide should wait for Page Down or Enter");
+/// ```
+pub fn set_advance_keys(keys: &[KeyCode]) {
+    *ADVANCE_KEYS
+        .write()
+        .expect("advance keys lock should not be poisoned") = Cow::Owned(keys.to_vec());
+}
+
+/// Returns the keys that are interactions for [`WaitForInteraction`](crate::WaitForInteraction).
+pub fn advance_keys() -> Vec<KeyCode> {
+    ADVANCE_KEYS
+        .read()
+        .expect("advance keys lock should not be poisoned")
+        .to_vec()
+}
+
+/// Sets the keys that ask to stop the presentation while waiting for an interaction, which are none by default.
+///
+/// Pressing one passes the wait, and [`quit_requested`] returns `true` from then on,
+/// so code showing standalone slides can stop cleanly.
+/// A running [`Presentation`](crate::Presentation) stops right away instead, skipping the rest of its slide,
+/// and `q` and Escape stop a looping one even if they're not quit keys.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::crossterm::event::KeyCode;
+/// use clp::{quit_requested, set_quit_keys, slide, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// set_quit_keys(&[KeyCode::Esc, KeyCode::Char('q')]);
+///
+/// for text in ["First slide", "Second slide", "Third slide"] {
+///     slide!(TypewriterPrint(text, Duration::from_millis(25))).expect("the slide should appear");
+///
+///     if quit_requested() {
+///         break;
+///     }
+/// }
+/// ```
+pub fn set_quit_keys(keys: &[KeyCode]) {
+    *QUIT_KEYS
+        .write()
+        .expect("quit keys lock should not be poisoned") = Cow::Owned(keys.to_vec());
+}
+
+/// Returns the keys that ask to stop the presentation while waiting for an interaction.
+pub fn quit_keys() -> Vec<KeyCode> {
+    QUIT_KEYS
+        .read()
+        .expect("quit keys lock should not be poisoned")
+        .to_vec()
+}
+
+/// Returns `true` if a quit key has been pressed, see [`set_quit_keys`].
+///
+/// This is reset whenever a [`Presentation`](crate::Presentation) starts running.
+pub fn quit_requested() -> bool {
+    QUIT_REQUESTED.load(Ordering::Relaxed)
+}
+
+/// Sets whether a quit key has been pressed.
+pub(crate) fn set_quit_requested(requested: bool) {
+    QUIT_REQUESTED.store(requested, Ordering::Relaxed);
+}
+
+/// What happens when Ctrl+C is pressed while waiting for an interaction or a key.
+///
+/// Raw mode is enabled meanwhile, so Ctrl+C doesn't send an interrupt signal to the process like it usually would.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CtrlC {
+    /// The terminal is restored with [`reset_terminal`] and the slide returns an [`Interrupted`] error,
+    /// which stops a running [`Presentation`](crate::Presentation) too.
+    /// Prompts return the error themselves, and [`quit_requested`] returns `true` from then on.
+    #[default]
+    Interrupt,
+    /// The terminal is restored with [`reset_terminal`] and the process exits with code 130,
+    /// like it would on an interrupt signal, without running any destructors.
+    Exit,
+    /// Nothing special happens, so Ctrl+C is like any other key.
+    Ignore,
+}
+
+/// Sets what happens when Ctrl+C is pressed while waiting for an interaction or a key,
+/// which is stopping the slide with an error by default.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{set_ctrl_c, slide, CtrlC, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// set_ctrl_c(CtrlC::Exit);
+///
+/// slide!(TypewriterPrint("Press Ctrl+C to leave at any time.", Duration::from_millis(25)))
+///     .expect("the slide should appear");
+/// ```
+pub fn set_ctrl_c(behavior: CtrlC) {
+    *CTRL_C.write().expect("Ctrl+C lock should not be poisoned") = behavior;
+}
+
+/// Returns what happens when Ctrl+C is pressed while waiting for an interaction or a key.
+pub fn ctrl_c() -> CtrlC {
+    *CTRL_C.read().expect("Ctrl+C lock should not be poisoned")
+}
+
+/// Reacts to `key` as set with [`set_ctrl_c`] if it's Ctrl+C, returning the error to stop with.
+///
+/// Returns [`None`] if `key` isn't Ctrl+C, or if Ctrl+C is ignored.
+pub(crate) fn press_ctrl_c(key: KeyEvent) -> Option<io::Error> {
+    if key.code != KeyCode::Char('c') || !key.modifiers.contains(KeyModifiers::CONTROL) {
+        return None;
+    }
+
+    let behavior = ctrl_c();
+
+    if behavior == CtrlC::Ignore {
+        return None;
+    }
+
+    let _ = reset_terminal();
+
+    if behavior == CtrlC::Exit {
+        process::exit(130);
+    }
+
+    set_quit_requested(true);
+
+    Some(io::Error::other(Interrupted))
+}
+
+/// The error returned when Ctrl+C is pressed, see [`CtrlC::Interrupt`].
+///
+/// It's returned as the inner error of an [`io::Error`] of kind [`Other`](io::ErrorKind::Other),
+/// since writes retry on errors of kind [`Interrupted`](io::ErrorKind::Interrupted) instead of returning them.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, Interrupted, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// if let Err(error) = slide!(TypewriterPrint("Hello, world!", Duration::from_millis(25))) {
+///     if error.get_ref().is_some_and(|error| error.is::<Interrupted>()) {
+///         println!("Goodbye!");
+///     } else {
+///         panic!("the slide should appear: {error}");
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interrupted;
+
+impl Display for Interrupted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Ctrl+C was pressed")
+    }
+}
+
+impl Error for Interrupted {}
+
+/// Returns `true` if `code` is one of the [`advance_keys`].
+pub(crate) fn is_advance_key(code: KeyCode) -> bool {
+    ADVANCE_KEYS
+        .read()
+        .expect("advance keys lock should not be poisoned")
+        .contains(&code)
+}
+
+/// Returns `true` if `code` is one of the [`quit_keys`].
+pub(crate) fn is_quit_key(code: KeyCode) -> bool {
+    QUIT_KEYS
+        .read()
+        .expect("quit keys lock should not be poisoned")
+        .contains(&code)
+}
+
+/// Returns the name of `code` as it's shown to the audience, like `Space` or `PageDown`.
+pub(crate) fn key_name(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(char) => char.to_string(),
+        code => format!("{code:?}"),
+    }
+}
+
+/// What happens when a key that doesn't do anything is pressed while waiting for an interaction.
+///
+/// This gives feedback to a presenter who hits the wrong key during a live talk and wonders why nothing happened.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum UnknownKey {
+    /// Nothing happens.
+    #[default]
+    Ignore,
+    /// The terminal bell rings.
+    Bell,
+    /// The given function is called with the key that was pressed.
+    Callback(fn(KeyEvent)),
+}
+
+impl PartialEq for UnknownKey {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Callback(callback), Self::Callback(other)) => ptr::fn_addr_eq(*callback, *other),
+            _ => mem::discriminant(self) == mem::discriminant(other),
+        }
+    }
+}
+
+/// Sets what happens when a key that doesn't do anything is pressed while waiting for an interaction,
+/// which is nothing by default.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{set_unknown_key, slide, TypewriterPrint, UnknownKey};
+/// use std::time::Duration;
+///
+/// set_unknown_key(UnknownKey::Bell);
+///
+/// slide!(TypewriterPrint("Press space, not anything else!", Duration::from_millis(25)))
+///     .expect("other keys should ring the bell");
+/// ```
+pub fn set_unknown_key(behavior: UnknownKey) {
+    *UNKNOWN_KEY
+        .write()
+        .expect("unknown key lock should not be poisoned") = behavior;
+}
+
+/// Returns what happens when a key that doesn't do anything is pressed while waiting for an interaction.
+pub fn unknown_key() -> UnknownKey {
+    *UNKNOWN_KEY
+        .read()
+        .expect("unknown key lock should not be poisoned")
+}
+
+/// Reacts to a key that doesn't do anything being pressed, as set with [`set_unknown_key`].
+pub(crate) fn press_unknown_key(f: &mut impl fmt::Write, key: KeyEvent) -> fmt::Result {
+    match unknown_key() {
+        UnknownKey::Ignore => Ok(()),
+        UnknownKey::Bell => {
+            f.write_char('\x07')?;
+            output::flush();
+
+            Ok(())
+        }
+        UnknownKey::Callback(callback) => {
+            callback(key);
+
+            Ok(())
+        }
+    }
+}
+
+/// Sets whether interactive prompts print the key that was accepted, which they don't by default.
+///
+/// Echoing makes it clear what was chosen, especially in recordings.
+pub fn set_prompt_echo(enabled: bool) {
+    ECHO.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether interactive prompts print the key that was accepted.
+pub fn prompt_echo() -> bool {
+    ECHO.load(Ordering::Relaxed)
+}
+
+/// Types `question`, then waits for one of the `options` to be pressed and returns it.
+///
+/// Letters match regardless of case, but the option is always returned as it was given.
+/// Since commands can't return anything, this is a function rather than a command,
+/// so it should be called between slides or from within a [`Presentation`](crate::Presentation) slide.
+/// While rendering headlessly, the first option is returned without waiting.
+/// If [`prompt_echo`] is enabled, the chosen option is printed after it's pressed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{prompt, slide, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// let answer = prompt("Do you want to see a demo? [y/n] ", &['y', 'n'], Duration::from_millis(25))
+///     .expect("the prompt should be answered");
+///
+/// if answer == 'y' {
+///     slide!(TypewriterPrint("Here's the demo!", Duration::from_millis(25)))
+///         .expect("the demo slide should appear");
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if `options` is empty, or if the terminal can't be written to or read from.
+pub fn prompt(question: impl Display, options: &[char], delay: Duration) -> io::Result<char> {
+    let Some(&first_option) = options.first() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "a prompt needs at least one option",
+        ));
+    };
+
+    execute!(output(), TypewriterPrint(question, delay))?;
+
+    if output::is_headless() {
+        return Ok(first_option);
+    }
+
+    let _raw_mode = RawModeGuard::enable()?;
+
+    let choice = loop {
+        if let Event::Key(key) = event::read()? {
+            if let Some(error) = press_ctrl_c(key) {
+                return Err(error);
+            }
+
+            if let KeyCode::Char(char) = key.code {
+                let choice = options.iter().find(|option| **option == char).or_else(|| {
+                    options
+                        .iter()
+                        .find(|option| option.to_lowercase().eq(char.to_lowercase()))
+                });
+
+                if let Some(&choice) = choice {
+                    break choice;
+                }
+            }
+        }
+    };
+
+    if prompt_echo() {
+        write!(output(), "{choice}")?;
+        output().flush()?;
+    }
+
+    Ok(choice)
+}
+
+/// Types `question`, then reads a line of text as it's typed in, echoing it, and returns it once Enter is pressed.
+///
+/// Backspace erases the last character, and Escape cancels the input, returning [`None`].
+/// Like [`prompt`], this is a function rather than a command since it returns what was typed in,
+/// so it should be called between slides or from within a [`Presentation`](crate::Presentation) slide.
+/// While rendering headlessly, an empty string is returned without waiting.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{input_prompt, slide, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// let answer = input_prompt("What's your favorite language? ", Duration::from_millis(25))
+///     .expect("the question should be answered");
+///
+/// if let Some(answer) = answer {
+///     slide!(TypewriterPrint(format!("{answer} is a great choice!"), Duration::from_millis(25)))
+///         .expect("the answer should be typed back");
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the terminal can't be written to or read from.
+pub fn input_prompt(question: impl Display, delay: Duration) -> io::Result<Option<String>> {
+    execute!(output(), TypewriterPrint(question, delay))?;
+
+    if output::is_headless() {
+        return Ok(Some(String::new()));
+    }
+
+    let _raw_mode = RawModeGuard::enable()?;
+
+    let mut answer = String::new();
+    let answer = loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+
+        if let Some(error) = press_ctrl_c(key) {
+            return Err(error);
+        }
+
+        match key.code {
+            KeyCode::Enter => break Some(answer),
+            KeyCode::Esc => break None,
+            KeyCode::Backspace => {
+                if let Some(char) = answer.pop() {
+                    let width = layout::width(char.encode_utf8(&mut [0; 4])) as u16;
+
+                    if width > 0 {
+                        execute!(output(), MoveLeft(width), Clear(ClearType::UntilNewLine))?;
+                    }
+                }
+            }
+            KeyCode::Char(char) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                answer.push(char);
+                write!(output(), "{char}")?;
+                output().flush()?;
+            }
+            _ => {}
+        }
+    };
+
+    Ok(answer)
+}
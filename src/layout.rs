@@ -0,0 +1,216 @@
+//! Layout of typewriter text, such as wrapping it to a fixed content width.
+
+use crate::markers::Segment;
+use crate::output;
+use crossterm::cursor::MoveToColumn;
+use crossterm::Command;
+use std::cell::Cell;
+use std::fmt;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+thread_local! {
+    static CONTENT_WIDTH: Cell<Option<u16>> = const { Cell::new(None) };
+}
+
+/// Sets the width that typewriter text is wrapped to and centered within, returning the previous width.
+pub(crate) fn set_content_width(width: Option<u16>) -> Option<u16> {
+    CONTENT_WIDTH.with(|content_width| content_width.replace(width))
+}
+
+/// Runs `render` with the content width set to `width`, restoring the previous width afterward,
+/// even if `render` panics.
+pub(crate) fn with_content_width<R>(width: Option<u16>, render: impl FnOnce() -> R) -> R {
+    struct Restore(Option<u16>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            set_content_width(self.0);
+        }
+    }
+
+    let _restore = Restore(set_content_width(width));
+
+    render()
+}
+
+/// Returns the column the content area starts at and its width, which is the whole terminal without a content width.
+pub(crate) fn content_area() -> (u16, u16) {
+    let terminal_width = output::terminal_size().map_or(u16::MAX, |(columns, _)| columns.max(1));
+
+    match CONTENT_WIDTH.with(Cell::get) {
+        Some(width) => (
+            terminal_width.saturating_sub(width) / 2,
+            width.min(terminal_width),
+        ),
+        None => (0, terminal_width),
+    }
+}
+
+/// Returns `true` if the terminal is at least `columns` wide and `rows` tall, or if its size can't be determined.
+pub(crate) fn fits(columns: u16, rows: u16) -> bool {
+    output::terminal_size().map_or(true, |(terminal_columns, terminal_rows)| {
+        terminal_columns >= columns && terminal_rows >= rows
+    })
+}
+
+/// Writes a message saying that the terminal needs to be at least `columns` wide and `rows` tall,
+/// unless it already is, returning whether it was written.
+///
+/// Commands that need a minimum size draw this instead of broken output.
+pub(crate) fn write_too_small(
+    f: &mut impl fmt::Write,
+    columns: u16,
+    rows: u16,
+) -> Result<bool, fmt::Error> {
+    if fits(columns, rows) {
+        return Ok(false);
+    }
+
+    let (terminal_columns, terminal_rows) = output::terminal_size().unwrap_or((columns, rows));
+
+    write!(
+        f,
+        "Terminal too small: needs at least {columns}x{rows}, but it's {terminal_columns}x{terminal_rows}."
+    )?;
+
+    Ok(true)
+}
+
+/// Returns how many columns `text` takes up in the terminal, counting wide characters as two columns.
+pub(crate) fn width(text: &str) -> usize {
+    text.width()
+}
+
+/// Word-wraps `text` to `width` columns, assuming the first line starts at `column`.
+///
+/// Words longer than `width` are broken up, and spaces that would overflow a line are dropped.
+/// Wide characters take up two columns, and are moved to the next line rather than split across two.
+pub(crate) fn wrap(text: &str, width: usize, mut column: usize) -> String {
+    let width = width.max(1);
+    let mut wrapped = String::with_capacity(text.len());
+
+    for (index, line) in text.split('\n').enumerate() {
+        if index > 0 {
+            wrapped.push('\n');
+            column = 0;
+        }
+
+        for word in line.split_inclusive(' ') {
+            let word_width = word.trim_end_matches(' ').width();
+
+            if column > 0 && word_width > 0 && column + word_width > width {
+                wrapped.push('\n');
+                column = 0;
+            }
+
+            for char in word.chars() {
+                let char_width = char.width().unwrap_or(0);
+
+                if column > 0 && column + char_width > width {
+                    if char == ' ' {
+                        continue;
+                    }
+
+                    wrapped.push('\n');
+                    column = 0;
+                }
+
+                wrapped.push(char);
+                column += char_width;
+            }
+        }
+    }
+
+    wrapped
+}
+
+/// Wraps and centers the text segments if a content width is set, leaving them as-is otherwise.
+pub(crate) fn arrange(segments: Vec<Segment>) -> Vec<Segment> {
+    let Some(width) = CONTENT_WIDTH.with(Cell::get) else {
+        return segments;
+    };
+    let terminal_width = output::terminal_size().map_or(width, |(columns, _)| columns);
+    let padding = terminal_width.saturating_sub(width) / 2;
+    let current_column = current_column();
+    let mut column = current_column.saturating_sub(padding) as usize;
+    let mut at_line_start = current_column < padding;
+    let mut move_to_padding = String::new();
+
+    MoveToColumn(padding)
+        .write_ansi(&mut move_to_padding)
+        .expect("cursor movement should be written to a string");
+
+    let mut arranged = Vec::with_capacity(segments.len());
+
+    for segment in segments {
+        let Segment::Text(text) = segment else {
+            arranged.push(segment);
+            continue;
+        };
+
+        for (index, line) in wrap(&text, width as usize, column).split('\n').enumerate() {
+            if index > 0 {
+                arranged.push(Segment::Text("\n".to_string()));
+                column = 0;
+                at_line_start = true;
+            }
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if at_line_start {
+                arranged.push(Segment::Raw(move_to_padding.clone()));
+                at_line_start = false;
+            }
+
+            column += line.width();
+            arranged.push(Segment::Text(line.to_string()));
+        }
+    }
+
+    arranged
+}
+
+/// Returns how many terminal rows `text` takes up once it's typed from the first column.
+///
+/// This accounts for wrapping to the content width, if there is one, and for wrapping by the terminal.
+pub(crate) fn rows(text: &str) -> u16 {
+    rows_from(text, 0)
+}
+
+/// Like [`rows`], but for `text` typed from `column` rather than from the first column.
+pub(crate) fn rows_from(text: &str, column: u16) -> u16 {
+    let text = match CONTENT_WIDTH.with(Cell::get) {
+        Some(width) => wrap(
+            text,
+            width as usize,
+            column.saturating_sub(content_area().0) as usize,
+        ),
+        None => text.to_string(),
+    };
+    let terminal_width =
+        output::terminal_size().map_or(u16::MAX, |(columns, _)| columns.max(1)) as usize;
+    let rows: usize = text
+        .split('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            let start = if index == 0 { column as usize } else { 0 };
+
+            (start + line.width()).div_ceil(terminal_width).max(1)
+        })
+        .sum();
+
+    u16::try_from(rows).unwrap_or(u16::MAX)
+}
+
+/// Returns the column the cursor is currently in, or `0` if it can't be determined.
+pub(crate) fn current_column() -> u16 {
+    if output::is_headless() {
+        return 0;
+    }
+
+    output::flush();
+
+    output::cursor_position().map_or(0, |(column, _)| column)
+}
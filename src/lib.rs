@@ -28,8 +28,11 @@
 //!
 //! # Features
 //!
-//! This package has one feature: `spin_sleep`. It enables the [`spin_sleep`](https://docs.rs/spin_sleep/) dependency,
-//! which is a more accurate drop-in replacement for the [`sleep`] function.
+//! This package has five features, described below.
+//!
+//! The `spin_sleep` feature enables the [`spin_sleep`](https://docs.rs/spin_sleep/) dependency,
+//! which is a more accurate drop-in replacement for the [`sleep`](std::thread::sleep) function,
+//! and makes `Sleeper::Accurate`, which uses it, the default [`Sleeper`].
 //! It's particularly useful on Windows, which has a notoriously inaccurate `sleep` function.
 //! If you notice that [`TypewriterPrint`] or [`TypewriterPrintStyledContent`] is slower than expected,
 //! you should enable the `spin_sleep` feature.
@@ -37,26 +40,159 @@
 //! ```bash
 //! cargo add clp -F spin_sleep
 //! ```
+//!
+//! With the feature enabled, the sleeper can also be chosen at runtime with [`set_sleeper`],
+//! so a single binary can sleep accurately on Windows and use the standard `sleep` elsewhere.
+//!
+//! The `figlet` feature enables the [`figlet-rs`](https://docs.rs/figlet-rs/) dependency and the `FigletPrint` command,
+//! which types text art made with a figlet font.
+//!
+//! ```bash
+//! cargo add clp -F figlet
+//! ```
+//!
+//! The `qrcode` feature enables the [`qrcode`](https://docs.rs/qrcode/) dependency and the `QrCode` command,
+//! which draws a scannable QR code on a slide.
+//!
+//! ```bash
+//! cargo add clp -F qrcode
+//! ```
+//!
+//! The `syntect` feature enables the [`syntect`](https://docs.rs/syntect/) dependency and the `CodeBlock` command,
+//! which types syntax-highlighted source code.
+//!
+//! ```bash
+//! cargo add clp -F syntect
+//! ```
+//!
+//! The `async` feature enables the `play_async` function, which plays commands from async code
+//! by awaiting timers instead of sleeping, so the executor isn't blocked.
+//! It doesn't depend on any particular async runtime,
+//! since its timers are woken by a single thread that it starts the first time one is awaited.
+//! Interactions aren't awaited: waits like [`WaitForInteraction`] and [`WaitForKey`] pass straight away
+//! and no keys are read, so the audience can't advance what's played, and the app playing it has to handle input itself.
+//!
+//! ```bash
+//! cargo add clp -F async
+//! ```
 
 #![warn(missing_docs)]
 
+mod card;
+mod centered;
+mod chart;
+mod checklist;
+mod cleanup;
+mod clock;
+#[cfg(feature = "syntect")]
+mod code;
+mod effects;
+mod estimate;
+#[cfg(feature = "figlet")]
+mod figlet;
+mod gradient;
+mod image;
+mod interaction;
+mod layout;
+mod list;
+mod locale;
+mod markdown;
+mod markers;
+mod output;
+mod overview;
+#[cfg(feature = "async")]
+mod playback;
+mod presentation;
+mod progress;
+#[cfg(feature = "qrcode")]
+mod qr;
+mod quote;
+mod random;
+mod recorder;
+mod reveal;
+mod runner;
+mod script;
+mod session;
+mod sleeper;
+mod slide;
+mod template;
+mod text;
+mod text_box;
+mod typing;
+
+pub use card::Card;
+#[allow(deprecated)]
+pub use centered::{Centered, CenteredScreen};
+pub use chart::BarChart;
+pub use checklist::{Advance, Checklist};
+pub use cleanup::{install_cleanup_hook, reset_terminal};
+#[doc(hidden)]
+pub use clock::SlideEnd as __SlideEnd;
+pub use clock::{Corner, Countdown, ElapsedTime};
+#[cfg(feature = "syntect")]
+pub use code::CodeBlock;
 pub use crossterm;
+pub use effects::{
+    BeforeAfter, DecodePrint, RevealMasked, ScrollingLog, Sequence, Toast, TypewriterErase,
+    WavePrint,
+};
+#[cfg(feature = "figlet")]
+pub use figlet::{register_figlet_font, FigletPrint};
+pub use gradient::GradientPrint;
+pub use image::{image_protocol, set_image_protocol, ImageProtocol, SlideImage};
+pub use interaction::{
+    advance_keys, ctrl_c, input_prompt, prompt, prompt_echo, quit_keys, quit_requested,
+    set_advance_keys, set_ctrl_c, set_prompt_echo, set_quit_keys, set_unknown_key, unknown_key,
+    CtrlC, Interrupted, UnknownKey,
+};
+pub use list::OrderedList;
+pub use locale::{add_locale, locale, set_locale, tr};
+pub use markdown::TypewriterMarkdown;
+pub use markers::{marker_syntax, set_marker_syntax, MarkerSyntax};
+pub use output::{
+    capture_output, output, output_batching, set_output, set_output_batching, Output,
+};
+#[doc(hidden)]
+pub use output::{redirect as __redirect_output, Redirect as __Redirect};
+#[cfg(feature = "async")]
+pub use playback::play_async;
+pub use presentation::{Presentation, SlideFailure, Splash};
+pub use progress::SlideProgress;
+#[cfg(feature = "qrcode")]
+pub use qr::{QrCode, QrCodeError};
+pub use quote::Quote;
+pub use random::seed_random;
+pub use recorder::Recorder;
+pub use reveal::Reveal;
+pub use script::{Action, EventScript};
+#[doc(hidden)]
+pub use session::start as __start_session;
+pub use session::{cursor_hiding, raw_mode, set_cursor_hiding, set_raw_mode, RawMode};
+pub use sleeper::{set_sleeper, sleeper, Sleeper};
+pub use slide::{Slide, SlideBuilder};
+pub use template::Template;
+pub use text::{newline_normalization, set_newline_normalization};
+pub use text_box::{Overflow, TextBox};
+pub use typing::{
+    max_typing_delay, set_max_typing_delay, set_speed_multiplier, set_typing_skip,
+    speed_multiplier, typewrite_with_callback, typing_skip, DelayTooLong, Direction, Easing,
+    Granularity, Typing, TypingSkip, Whitespace,
+};
 
 use crossterm::event::{self, Event, KeyCode};
-use crossterm::style::{PrintStyledContent, StyledContent, Stylize};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled};
+use crossterm::style::{PrintStyledContent, StyledContent};
 use crossterm::Command;
-#[cfg(feature = "spin_sleep")]
-use spin_sleep::sleep;
+use markers::Segment;
+use runner::Interrupt;
 use std::fmt::{self, Display, Formatter};
-use std::io::{stdout, Write as _};
-#[cfg(not(feature = "spin_sleep"))]
-use std::thread::sleep;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use typing::{pause, typewrite};
+use unicode_segmentation::UnicodeSegmentation;
 
 /// Defines a slide and shows it.
 ///
 /// Takes any number of [`Command`]s as arguments.
+/// The slide is written to [`output`], which is standard output unless changed with [`set_output`].
 ///
 /// # Examples
 ///
@@ -81,20 +217,137 @@ use std::time::Duration;
 /// ))
 /// .expect("the second slide should appear");
 /// ```
+///
+/// By default, the slide ends with [`WaitForInteraction`].
+/// A different command to end it with can be given first with `wait: <command>;`,
+/// or `wait: none;` to not wait at all:
+///
+/// ```no_run
+/// use clp::{slide, TypewriterPrint, WaitFor};
+/// use std::time::Duration;
+///
+/// slide!(
+///     wait: WaitFor(Duration::from_secs(3));
+///     TypewriterPrint("This slide advances by itself.", Duration::from_millis(25)),
+/// )
+/// .expect("the timed slide should appear");
+///
+/// slide!(
+///     wait: none;
+///     TypewriterPrint("This slide doesn't wait at all.", Duration::from_millis(25)),
+/// )
+/// .expect("the transient slide should appear");
+/// ```
+///
+/// The screen is cleared before the slide is shown, unless `clear: false;` is given first,
+/// which keeps what's already on the screen so a slide can be built up across several calls.
+/// It can be combined with `wait: <command>;`, which comes after it:
+///
+/// ```no_run
+/// use clp::{slide, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// slide!(TypewriterPrint("Three reasons to use the terminal:", Duration::from_millis(25)))
+///     .expect("the heading should appear");
+///
+/// for reason in ["\n- It's fast", "\n- It's everywhere", "\n- It's fun"] {
+///     slide!(clear: false; TypewriterPrint(reason, Duration::from_millis(25)))
+///         .expect("the reason should be added below the others");
+/// }
+/// ```
+///
+/// Within a running [`Presentation`], a slide shown again after the help overlay, the overview or a resize
+/// is shown from where it started, with everything below that cleared first.
+/// What earlier slides of the presentation left on the screen isn't shown again,
+/// so a slide that starts with `clear: false;` may lose the parts of it that were covered or cleared.
 #[macro_export]
 macro_rules! slide {
-    ($($command:expr),* $(,)?) => {{
-        use $crate::crossterm::execute;
+    (@show $clear:expr; $($command:expr),*) => {{
         use $crate::crossterm::terminal::{Clear, ClearType};
+        use $crate::crossterm::{execute, queue};
         use $crate::WaitForInteraction;
-        use std::io::stdout;
 
-        execute!(stdout(), Clear(ClearType::All), $($command,)* WaitForInteraction)
-    }}
+        let _slide_end = $crate::__SlideEnd;
+        let _session = $crate::__start_session();
+        let cleared = if $clear {
+            queue!($crate::output(), Clear(ClearType::All))
+        } else {
+            Ok(())
+        };
+
+        cleared.and_then(|()| execute!($crate::output() $(, $command)*))
+    }};
+    (clear: $clear:expr; wait: none; $($command:expr),* $(,)?) => {
+        $crate::slide!(@show $clear; $($command),*)
+    };
+    (clear: $clear:expr; wait: $wait:expr; $($command:expr),* $(,)?) => {
+        $crate::slide!(@show $clear; $($command,)* $wait)
+    };
+    (clear: $clear:expr; $($command:expr),* $(,)?) => {
+        $crate::slide!(@show $clear; $($command,)* WaitForInteraction)
+    };
+    (wait: $($rest:tt)*) => {
+        $crate::slide!(clear: true; wait: $($rest)*)
+    };
+    ($($command:expr),* $(,)?) => {
+        $crate::slide!(clear: true; $($command),*)
+    };
+}
+
+/// Defines a slide and shows it on the given stream, like [`slide`] but without changing the [`output`] for good.
+///
+/// The first argument is the stream, which has to be able to tell whether it's a terminal,
+/// like [`Stderr`](std::io::Stderr) or the [`File`](std::fs::File) of `/dev/tty`,
+/// and the rest are the same as those of [`slide`].
+/// This is useful for tools whose standard output is piped somewhere, like into a file capturing data,
+/// that still want to present on the terminal they were started from.
+/// Once the slide is over, slides are written to the previous output again.
+///
+/// Raw mode is enabled by default if the stream is a terminal, see [`set_raw_mode`],
+/// and keys are still read from the terminal the process was started from,
+/// which is standard input if it's a terminal or `/dev/tty` otherwise.
+/// Commands that need to know where the cursor is, like [`TypewriterErase`],
+/// assume it's at the start of a line while standard output isn't a terminal,
+/// since asking the terminal is done through standard output.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide_to, TypewriterPrint};
+/// use std::fs::OpenOptions;
+/// use std::time::Duration;
+///
+/// let tty = OpenOptions::new()
+///     .write(true)
+///     .open("/dev/tty")
+///     .expect("the terminal should be opened");
+///
+/// slide_to!(tty, TypewriterPrint("Hello, terminal!", Duration::from_millis(25)))
+///     .expect("\"Hello, terminal!\" should be typed to the terminal even if standard output is piped");
+/// ```
+#[macro_export]
+macro_rules! slide_to {
+    ($target:expr $(,)?) => {
+        $crate::slide_to!($target,)
+    };
+    ($target:expr, $($rest:tt)*) => {{
+        let _redirect: $crate::__Redirect = $crate::__redirect_output($target);
+
+        $crate::slide!($($rest)*)
+    }};
 }
 
 /// A command that prints the given displayable type, one character at a time.
 ///
+/// Pause markers such as `{{pause:500}}` are consumed rather than printed, see [`MarkerSyntax`].
+/// `\r\n` and lone `\r` line endings are printed as `\n`, see [`set_newline_normalization`].
+/// ANSI escape sequences already in the text, like those of other styling crates,
+/// are printed all at once and take no time to type.
+///
+/// The second field is usually a [`Duration`], the delay between characters,
+/// but it can be any [`Typing`] options for more control over how the text is typed.
+/// A delay of [`Duration::ZERO`] prints the text instantly, all at once rather than one character at a time.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -111,28 +364,173 @@ macro_rules! slide {
 /// (which [`TypewriterPrint`] is when in [`slide`])
 /// otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct TypewriterPrint<T: Display>(pub T, pub Duration);
-
-impl<T: Display> Command for TypewriterPrint<T> {
-    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        for char in self.0.to_string().chars() {
-            f.write_char(char)?;
-            stdout()
-                .flush()
-                .expect("standard output stream should flush");
-
-            if !is_raw_mode_enabled().expect("should check if raw mode is enabled") {
-                enable_raw_mode().expect("raw mode should enable");
-            }
+pub struct TypewriterPrint<T: Display, O: Into<Typing> + Copy = Duration>(pub T, pub O);
 
-            sleep(self.1);
+impl<T: Display> TypewriterPrint<T> {
+    /// Creates a command that types `text` with `delay` between characters,
+    /// unless `delay` is longer than the [`max_typing_delay`].
+    ///
+    /// This catches delays that are far too long, like those of an arithmetic mistake,
+    /// which would otherwise make a slide look like it hangs.
+    /// Creating the command directly, like `TypewriterPrint(text, delay)`, doesn't check the delay.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, TypewriterPrint};
+    /// use std::time::Duration;
+    ///
+    /// let typewriter = TypewriterPrint::try_new("Hello, world!", Duration::from_millis(25))
+    ///     .expect("25ms should be a sane delay");
+    ///
+    /// slide!(typewriter).expect("\"Hello, world!\" should be typed");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `delay` is longer than the [`max_typing_delay`].
+    pub fn try_new(text: T, delay: Duration) -> Result<Self, DelayTooLong> {
+        let max = max_typing_delay();
 
-            if is_raw_mode_enabled().expect("should check if raw mode is enabled") {
-                disable_raw_mode().expect("raw mode should disable");
-            }
+        if delay > max {
+            return Err(DelayTooLong { delay, max });
         }
 
-        Ok(())
+        Ok(Self(text, delay))
+    }
+
+    /// Creates a command that finishes typing `text` in `total`, by spreading the delay evenly between its characters.
+    ///
+    /// This is useful for syncing typing to something with a known length, like narration.
+    /// Pause markers don't count as characters, and their pauses are added on top of `total`.
+    /// If `text` is empty, there's nothing to type, so no time is spent at all.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, TypewriterPrint};
+    /// use std::time::Duration;
+    ///
+    /// slide!(TypewriterPrint::over("Hello, world!", Duration::from_secs(2)))
+    ///     .expect("\"Hello, world!\" should be typed in two seconds");
+    /// ```
+    pub fn over(text: T, total: Duration) -> Self {
+        let characters: usize =
+            text::split_escapes(markers::parse(&text::normalize_newlines(&text.to_string())))
+                .iter()
+                .map(|segment| match segment {
+                    markers::Segment::Text(content) => content.chars().count(),
+                    _ => 0,
+                })
+                .sum();
+        let delay = match u32::try_from(characters) {
+            Ok(0) => Duration::ZERO,
+            Ok(characters) => total / characters,
+            Err(_) => total / u32::MAX,
+        };
+
+        Self(text, delay)
+    }
+}
+
+impl<T: Display> TypewriterPrint<T, Typing> {
+    /// Creates a command that changes the delay between characters from `start` to `end`
+    /// over the course of `text`, following `easing`.
+    ///
+    /// This is a shorthand for [`Typing::with_easing`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, Easing, TypewriterPrint};
+    /// use std::time::Duration;
+    ///
+    /// slide!(TypewriterPrint::with_easing(
+    ///     "Slowly at first, then faster and faster...",
+    ///     Duration::from_millis(120),
+    ///     Duration::from_millis(10),
+    ///     Easing::EaseIn,
+    /// ))
+    /// .expect("the text should speed up as it's typed");
+    /// ```
+    pub fn with_easing(text: T, start: Duration, end: Duration, easing: Easing) -> Self {
+        Self(text, Typing::inherit().with_easing(start, end, easing))
+    }
+
+    /// Creates a command that types `text` with `delay` between characters,
+    /// multiplied by `comma` after `,`, `;` and `:`, and by `sentence` after `.`, `!`, `?` and line breaks.
+    ///
+    /// This is a shorthand for [`Typing::with_punctuation_pause`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, TypewriterPrint};
+    /// use std::time::Duration;
+    ///
+    /// slide!(TypewriterPrint::with_punctuation_pause(
+    ///     "Well, that went well. Didn't it?!",
+    ///     Duration::from_millis(25),
+    ///     4.0,
+    ///     12.0,
+    /// ))
+    /// .expect("the text should be typed with pauses after its punctuation");
+    /// ```
+    pub fn with_punctuation_pause(text: T, delay: Duration, comma: f32, sentence: f32) -> Self {
+        Self(
+            text,
+            Typing::new(delay).with_punctuation_pause(comma, sentence),
+        )
+    }
+
+    /// Creates a command that types `text` with `delay` between characters,
+    /// randomly lengthened or shortened by up to `jitter` after each one, for a more human feel.
+    ///
+    /// This is a shorthand for [`Typing::with_jitter`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, TypewriterPrint};
+    /// use std::time::Duration;
+    ///
+    /// slide!(TypewriterPrint::with_jitter(
+    ///     "This looks like someone is actually typing it.",
+    ///     Duration::from_millis(40),
+    ///     Duration::from_millis(20),
+    /// ))
+    /// .expect("the text should be typed at an uneven pace");
+    /// ```
+    pub fn with_jitter(text: T, delay: Duration, jitter: Duration) -> Self {
+        Self(text, Typing::new(delay).with_jitter(jitter))
+    }
+
+    /// Creates a command that types `text` with `delay` between characters,
+    /// ringing the terminal bell after every `every` characters.
+    ///
+    /// This is a shorthand for [`Typing::with_bell_every`], with whitespace counting as characters.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, TypewriterPrint};
+    /// use std::time::Duration;
+    ///
+    /// slide!(TypewriterPrint::with_bell_every(
+    ///     "Clack, clack, clack.",
+    ///     Duration::from_millis(80),
+    ///     4,
+    /// ))
+    /// .expect("the bell should ring after every fourth character");
+    /// ```
+    pub fn with_bell_every(text: T, delay: Duration, every: usize) -> Self {
+        Self(text, Typing::new(delay).with_bell_every(every, true))
+    }
+}
+
+impl<T: Display, O: Into<Typing> + Copy> Command for TypewriterPrint<T, O> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        typewrite(f, &self.0.to_string(), None, self.1.into())
     }
 
     #[cfg(windows)]
@@ -146,15 +544,55 @@ impl<T: Display> Command for TypewriterPrint<T> {
     }
 }
 
-impl<T: Display> Display for TypewriterPrint<T> {
+impl<T: Display, O: Into<Typing> + Copy> Display for TypewriterPrint<T, O> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
     }
 }
 
+/// A command that prints text one full line at a time, with the specified delay after each line break.
+///
+/// This is a shorthand for [`TypewriterPrint`] with [`Granularity::Lines`],
+/// and pause markers, line endings and ANSI escape sequences are handled the same way.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, TypewriterPrintLines};
+/// use std::time::Duration;
+///
+/// slide!(TypewriterPrintLines("- a\n- b\n- c", Duration::from_millis(400)))
+///     .expect("each bullet should pop in 400ms after the previous one");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`TypewriterPrintLines`] is when in [`slide`])
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypewriterPrintLines<T: Display>(pub T, pub Duration);
+
+impl<T: Display> Command for TypewriterPrintLines<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        typewrite(f, &self.0.to_string(), None, Typing::new(self.1).by_lines())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute TypewriterPrintLines command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
 /// A command that prints styled content, one character at a time.
 ///
 /// See [`StyledContent`] for more info.
+/// Pause markers, line endings and [`Typing`] options are handled the same way as in [`TypewriterPrint`].
 ///
 /// # Examples
 ///
@@ -176,28 +614,19 @@ impl<T: Display> Display for TypewriterPrint<T> {
 /// (which [`TypewriterPrintStyledContent`] is when in [`slide`])
 /// otherwise they do nothing.
 #[derive(Debug, Clone, Copy)]
-pub struct TypewriterPrintStyledContent<D: Display>(pub StyledContent<D>, pub Duration);
+pub struct TypewriterPrintStyledContent<D: Display, O: Into<Typing> + Copy = Duration>(
+    pub StyledContent<D>,
+    pub O,
+);
 
-impl<D: Display> Command for TypewriterPrintStyledContent<D> {
+impl<D: Display, O: Into<Typing> + Copy> Command for TypewriterPrintStyledContent<D, O> {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        for char in self.0.to_string().chars() {
-            PrintStyledContent(char.stylize()).write_ansi(f)?;
-            stdout()
-                .flush()
-                .expect("standard output stream should flush");
-
-            if !is_raw_mode_enabled().expect("should check if raw mode is enabled") {
-                enable_raw_mode().expect("raw mode should enable");
-            }
-
-            sleep(self.1);
-
-            if is_raw_mode_enabled().expect("should check if raw mode is enabled") {
-                disable_raw_mode().expect("raw mode should disable");
-            }
-        }
-
-        Ok(())
+        typewrite(
+            f,
+            &self.0.content().to_string(),
+            Some(*self.0.style()),
+            self.1.into(),
+        )
     }
 
     #[cfg(windows)]
@@ -206,20 +635,93 @@ impl<D: Display> Command for TypewriterPrintStyledContent<D> {
     }
 }
 
-impl Display for TypewriterPrintStyledContent<String> {
+impl<O: Into<Typing> + Copy> Display for TypewriterPrintStyledContent<String, O> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         PrintStyledContent(self.0.clone()).fmt(f)
     }
 }
 
-impl Display for TypewriterPrintStyledContent<&'static str> {
+impl<O: Into<Typing> + Copy> Display for TypewriterPrintStyledContent<&'static str, O> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        PrintStyledContent(self.0.clone()).fmt(f)
+        PrintStyledContent(self.0).fmt(f)
+    }
+}
+
+/// A command that types text one character per interaction, instead of on a timer.
+///
+/// Before each character, it waits for an interaction, just like [`WaitForInteraction`] does.
+/// Whitespace is typed along with the character after it, so it doesn't take an interaction of its own.
+/// Once the last character is typed, the slide goes on as usual,
+/// so a [`slide`] ends up waiting for its final interaction like it normally would.
+/// Pause markers, line endings and ANSI escape sequences are handled the same way as in [`TypewriterPrint`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, ManualTypewriter};
+///
+/// slide!(ManualTypewriter("let answer = 42;"))
+///     .expect("each character should be typed after an interaction");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`ManualTypewriter`] is when in [`slide`])
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ManualTypewriter<T: Display>(pub T);
+
+impl<T: Display> Command for ManualTypewriter<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let segments = layout::arrange(text::split_escapes(markers::parse(
+            &text::normalize_newlines(&self.0.to_string()),
+        )));
+
+        for segment in segments {
+            match segment {
+                Segment::Text(content) => {
+                    for grapheme in content.graphemes(true) {
+                        if !grapheme.chars().all(char::is_whitespace) {
+                            WaitForInteraction.write_ansi(f)?;
+                        }
+
+                        if runner::is_interrupted() {
+                            return Ok(());
+                        }
+
+                        typing::write_grapheme(f, grapheme, None)?;
+                    }
+                }
+                Segment::Pause(duration) => pause(duration),
+                Segment::Raw(raw) => f.write_str(&raw)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        Ok(())
     }
 }
 
 /// A command that waits for user interaction before executing subsequent commands.
 ///
+/// Enter, Right and Space are interactions, unless they're changed with [`set_advance_keys`].
+/// Pressing a key set with [`set_quit_keys`] passes the wait too, but asks to stop, see [`quit_requested`].
+/// What happens when any other key is pressed can be set with [`set_unknown_key`],
+/// except for Ctrl+C, which restores the terminal and stops the slide with an error unless changed with [`set_ctrl_c`].
+/// Within a running [`Presentation`], `?` and `h` show an overlay listing the keys it understands,
+/// `b` blanks the screen until any key is pressed, `o` shows an overview for jumping to any slide,
+/// and Left or Backspace undo the last interaction of the slide, like hiding the last item revealed,
+/// or go back to the previous slide if there's nothing to undo, showing it again from the start.
+/// Resizing the terminal during the wait shows the slide again up to the wait, laid out for the new size,
+/// so centered text, figlet banners and the like don't end up garbled.
+/// While a presentation is looping (see [`Presentation::run_loop`]), it passes by itself after a while,
+/// and `q` or Escape stop the loop instead.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -243,37 +745,361 @@ impl Display for TypewriterPrintStyledContent<&'static str> {
 pub struct WaitForInteraction;
 
 impl Command for WaitForInteraction {
-    fn write_ansi(&self, _f: &mut impl fmt::Write) -> fmt::Result {
-        stdout()
-            .flush()
-            .expect("standard output stream should flush");
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        wait_for_key(f, interaction::is_advance_key, None)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that marks the end of a fragment of a slide,
+/// waiting for an interaction before the next fragment is revealed below it.
+///
+/// This waits exactly like [`WaitForInteraction`], and nothing on the screen is cleared,
+/// so each interaction reveals one more fragment, like an incremental build in other presentation software.
+/// Within a running [`Presentation`], Left or Backspace hide the last fragment that was revealed.
+/// Fragments only go between the parts of a slide:
+/// [`slide`] already waits for an interaction after the last one,
+/// so a slide ending with [`Fragment`] waits for two interactions before it's over.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{crossterm, slide, Fragment};
+/// use crossterm::style::Print;
+///
+/// slide!(
+///     Print("Point 1\n"),
+///     Fragment,
+///     Print("Point 2\n"),
+///     Fragment,
+///     Print("Point 3"),
+/// )
+/// .expect("each point should be revealed after an interaction");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`Fragment`] is when in [`slide`])
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fragment;
+
+impl Command for Fragment {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        WaitForInteraction.write_ansi(f)
+    }
 
-        if !is_raw_mode_enabled().expect("should check if raw mode is enabled") {
-            enable_raw_mode().expect("raw mode should enable");
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that waits for a specific key to be pressed before executing subsequent commands,
+/// ignoring every other key.
+///
+/// This is useful for scripted demos, where the slide should only go on when the presenter means it to.
+/// Within a running [`Presentation`], the keys that show the help overlay or blank the screen
+/// still work, unless they're the key being waited for.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{crossterm, slide, WaitForKey};
+/// use crossterm::event::KeyCode;
+/// use crossterm::style::Print;
+///
+/// slide!(
+///     Print("Press n to continue.\n"),
+///     WaitForKey(KeyCode::Char('n')),
+///     Print("n was pressed."),
+/// )
+/// .expect("the second message should print after n is pressed");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`WaitForKey`] is when in [`slide`])
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitForKey(pub KeyCode);
+
+impl Command for WaitForKey {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        wait_for_key(f, |code| code == self.0, None)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that waits for any key to be pressed before executing subsequent commands.
+///
+/// This is friendlier than [`WaitForInteraction`] for demos where whoever is at the keyboard
+/// should just hit something to continue.
+/// Modifier keys pressed by themselves, like Shift, don't pass the wait, and neither do events that aren't keys,
+/// like focus changes and pastes.
+/// Keys set with [`set_quit_keys`] still pass the wait asking to stop, see [`quit_requested`],
+/// and within a running [`Presentation`], every other key passes the wait too,
+/// including those that would otherwise show the help overlay or blank the screen.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{crossterm, slide, WaitForAnyKey};
+/// use crossterm::style::Print;
+///
+/// slide!(
+///     Print("Press any key to continue.\n"),
+///     WaitForAnyKey,
+///     Print("A key was pressed."),
+/// )
+/// .expect("the second message should print after any key is pressed");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`WaitForAnyKey`] is when in [`slide`])
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitForAnyKey;
+
+impl Command for WaitForAnyKey {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        wait_for_key(f, |code| !matches!(code, KeyCode::Modifier(_)), None)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that waits for user interaction like [`WaitForInteraction`],
+/// but only for up to the specified duration, after which subsequent commands are executed anyway.
+///
+/// This is useful for self-running demos that can still be advanced early.
+/// Keys that don't pass the wait, like those that show the help overlay, don't extend it,
+/// so the whole wait never takes longer than the duration.
+/// While rendering headlessly, the duration counts as a delay rather than as an interaction.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{crossterm, slide, WaitForInteractionOrTimeout};
+/// use crossterm::style::Print;
+/// use std::time::Duration;
+///
+/// slide!(
+///     Print("This will appear immediately.\n"),
+///     WaitForInteractionOrTimeout(Duration::from_secs(10)),
+///     Print("This will appear after an interaction, or after 10 seconds."),
+/// )
+/// .expect("the second message should print after an interaction or 10 seconds");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`WaitForInteractionOrTimeout`] is when in [`slide`])
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitForInteractionOrTimeout(pub Duration);
+
+impl Command for WaitForInteractionOrTimeout {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        wait_for_key(f, interaction::is_advance_key, Some(self.0))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        Ok(())
+    }
+}
+
+/// Waits in raw mode until a key for which `advance` returns `true` is pressed.
+///
+/// Within a running [`Presentation`], the other keys it understands interrupt the slide instead,
+/// the elapsed time is kept up to date if it's shown,
+/// and the wait passes by itself once the slide has been shown for too long.
+/// The wait passes by itself after `timeout` if it's set,
+/// and while a presentation is looping, after the loop's delay, and quit keys stop it.
+/// If raw mode can't be enabled or keys can't be read, the wait ends and the output returns the error.
+fn wait_for_key(
+    f: &mut impl fmt::Write,
+    advance: impl Fn(KeyCode) -> bool,
+    timeout: Option<Duration>,
+) -> fmt::Result {
+    output::flush();
+
+    if output::is_headless() {
+        match timeout {
+            Some(timeout) => estimate::add_delay(timeout),
+            None => estimate::add_interaction(),
         }
 
-        loop {
-            if let Event::Key(key) = event::read().expect("should read event") {
-                if let KeyCode::Enter | KeyCode::Right | KeyCode::Char(' ') = key.code {
+        return Ok(());
+    }
+
+    if runner::skip_wait() {
+        return Ok(());
+    }
+
+    runner::caught_up();
+
+    if runner::is_manual() {
+        runner::stop();
+        output::start_discarding();
+
+        return Ok(());
+    }
+
+    if !session::acquire_raw_mode() {
+        return Ok(());
+    }
+
+    let waiting = Instant::now();
+    // The wait passes by itself at the same time no matter how many keys are pressed that don't pass it.
+    let deadline = [timeout, runner::advance_after()]
+        .into_iter()
+        .flatten()
+        .min()
+        .map(|timeout| waiting + timeout);
+
+    loop {
+        let mut timeout = None;
+
+        if let Some(progress) = runner::progress() {
+            progress.write_ansi(f)?;
+        }
+
+        if let Some(corner) = runner::clock() {
+            clock::draw(f, corner)?;
+            timeout = Some(
+                Duration::from_secs(1)
+                    - Duration::from_nanos(runner::elapsed().subsec_nanos().into()),
+            );
+        }
+
+        if let Some(until_change) = clock::draw_countdown(f)? {
+            timeout =
+                Some(timeout.map_or(until_change, |timeout: Duration| timeout.min(until_change)));
+        }
+
+        if let Some(deadline) = runner::slide_deadline() {
+            let Some(until_change) = clock::overtime(f, deadline)? else {
+                runner::pass_wait();
+                break;
+            };
+
+            timeout =
+                Some(timeout.map_or(until_change, |timeout: Duration| timeout.min(until_change)));
+        }
+
+        if let Some(deadline) = deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            if remaining.is_zero() {
+                runner::pass_wait();
+                break;
+            }
+
+            timeout = Some(timeout.map_or(remaining, |timeout: Duration| timeout.min(remaining)));
+        }
+
+        if let Some(timeout) = timeout {
+            output::flush();
+
+            match event::poll(timeout) {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(error) => {
+                    output::fail(error);
                     break;
                 }
             }
         }
 
-        if is_raw_mode_enabled().expect("should check if raw mode is enabled") {
-            disable_raw_mode().expect("raw mode should disable");
+        let event = match event::read() {
+            Ok(event) => event,
+            Err(error) => {
+                output::fail(error);
+                break;
+            }
+        };
+
+        // What's on screen is laid out for the old size, so the slide is shown again from the start up to this wait.
+        if matches!(event, Event::Resize(_, _)) && runner::is_running() {
+            runner::interrupt(Interrupt::Resize);
+            break;
         }
 
-        Ok(())
-    }
+        if let Event::Key(key) = event {
+            if let Some(error) = interaction::press_ctrl_c(key) {
+                output::fail(error);
+                break;
+            }
 
-    #[cfg(windows)]
-    fn execute_winapi(&self) -> crossterm::Result<()> {
-        Ok(())
+            match key.code {
+                code if interaction::is_quit_key(code)
+                    || runner::advance_after().is_some()
+                        && matches!(code, KeyCode::Char('q') | KeyCode::Esc) =>
+                {
+                    interaction::set_quit_requested(true);
+
+                    if runner::is_running() || runner::advance_after().is_some() {
+                        runner::interrupt(Interrupt::Quit);
+                    }
+
+                    break;
+                }
+                code if advance(code) => {
+                    runner::pass_wait();
+                    break;
+                }
+                KeyCode::Char('?' | 'h') if runner::is_running() => {
+                    runner::interrupt(Interrupt::Help);
+                    break;
+                }
+                KeyCode::Char('b') if runner::is_running() => {
+                    runner::interrupt(Interrupt::BlackScreen);
+                    break;
+                }
+                KeyCode::Char('o') if runner::is_running() => {
+                    runner::interrupt(Interrupt::Overview);
+                    break;
+                }
+                KeyCode::Left | KeyCode::Backspace if runner::is_running() => {
+                    runner::interrupt(Interrupt::StepBack);
+                    break;
+                }
+                _ => interaction::press_unknown_key(f, key)?,
+            }
+        }
     }
+
+    session::release_raw_mode();
+
+    Ok(())
 }
 
-/// A command that waits for the specified duration before executing subsequent commands.
+/// A command that waits for the specified duration before executing subsequent commands,
+/// or until an interaction if that comes first.
+///
+/// Unlike [`WaitForInteractionOrTimeout`], this is a delay rather than a wait for an interaction:
+/// it's skipped along with the other delays when a slide is restored or fast-forwarded,
+/// and it isn't a step that can be undone within a running [`Presentation`].
+/// Only the keys that are interactions for [`WaitForInteraction`] end it early, every other key is ignored.
+/// To wait for the whole duration regardless, use [`WaitFor::uninterruptible`].
 ///
 /// # Examples
 ///
@@ -285,7 +1111,7 @@ impl Command for WaitForInteraction {
 /// slide!(
 ///     Print("This will appear immediately.\n"),
 ///     WaitFor(Duration::from_secs(5)),
-///     Print("This will appear after 5 seconds."),
+///     Print("This will appear after 5 seconds, or sooner if you're in a hurry."),
 /// )
 /// .unwrap();
 /// ```
@@ -298,21 +1124,55 @@ impl Command for WaitForInteraction {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct WaitFor(pub Duration);
 
+impl WaitFor {
+    /// Creates a command that waits for the whole of `duration`, even if there's an interaction before then.
+    pub fn uninterruptible(duration: Duration) -> UninterruptibleWaitFor {
+        UninterruptibleWaitFor(duration)
+    }
+}
+
 impl Command for WaitFor {
     fn write_ansi(&self, _f: &mut impl fmt::Write) -> fmt::Result {
-        stdout()
-            .flush()
-            .expect("standard output stream should flush");
+        pause_until_advanced(self.0);
 
-        if !is_raw_mode_enabled().expect("should check if raw mode is enabled") {
-            enable_raw_mode().expect("raw mode should enable");
-        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        Ok(())
+    }
+}
 
-        sleep(self.0);
+/// A command that waits for the specified duration before executing subsequent commands,
+/// without being cut short by interactions like [`WaitFor`] is.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{crossterm, slide, WaitFor};
+/// use crossterm::style::Print;
+/// use std::time::Duration;
+///
+/// slide!(
+///     Print("This will appear immediately.\n"),
+///     WaitFor::uninterruptible(Duration::from_secs(5)),
+///     Print("This will appear after 5 seconds, no matter what."),
+/// )
+/// .expect("the second message should print after exactly 5 seconds");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`UninterruptibleWaitFor`] is when in [`slide`])
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UninterruptibleWaitFor(pub Duration);
 
-        if is_raw_mode_enabled().expect("should check if raw mode is enabled") {
-            disable_raw_mode().expect("raw mode should disable");
-        }
+impl Command for UninterruptibleWaitFor {
+    fn write_ansi(&self, _f: &mut impl fmt::Write) -> fmt::Result {
+        pause(self.0);
 
         Ok(())
     }
@@ -322,3 +1182,52 @@ impl Command for WaitFor {
         Ok(())
     }
 }
+
+/// Waits for `duration` like [`pause`], but stops early if one of the [`advance_keys`] is pressed.
+fn pause_until_advanced(duration: Duration) {
+    let duration = typing::at_speed(duration);
+    let deadline = Instant::now() + duration;
+
+    output::flush();
+
+    if output::is_headless() {
+        estimate::add_delay(duration);
+
+        return;
+    }
+
+    if runner::is_skipping_delays() || !session::acquire_raw_mode() {
+        return;
+    }
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+
+        if remaining.is_zero() {
+            break;
+        }
+
+        let timeout =
+            clock::update_countdown().map_or(remaining, |until_change| until_change.min(remaining));
+
+        match event::poll(timeout).and_then(|ready| ready.then(event::read).transpose()) {
+            Ok(Some(Event::Key(key))) => {
+                if let Some(error) = interaction::press_ctrl_c(key) {
+                    output::fail(error);
+                    break;
+                }
+
+                if interaction::is_advance_key(key.code) {
+                    break;
+                }
+            }
+            Ok(_) => {}
+            Err(error) => {
+                output::fail(error);
+                break;
+            }
+        }
+    }
+
+    session::release_raw_mode();
+}
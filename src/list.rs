@@ -0,0 +1,109 @@
+//! Lists that are numbered automatically.
+
+use crate::layout::{self, content_area, with_content_width};
+use crate::typing::typewrite;
+use crate::{text, Typing, WaitForInteraction};
+use crossterm::cursor::MoveToColumn;
+use crossterm::Command;
+use std::fmt::{self, Display};
+
+/// A command that types a numbered list, like `1. item`, numbering the items automatically.
+///
+/// Numbers are right-aligned, so the items of lists with ten or more of them line up,
+/// and items that wrap continue beneath their text rather than beneath their number.
+/// Items are wrapped to the content width of the running [`Presentation`](crate::Presentation)
+/// (see [`Presentation::content_width`](crate::Presentation::content_width)), or to the terminal's width otherwise.
+/// If [`reveal`](OrderedList::reveal) is set, each item after the first is only typed after an interaction.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, OrderedList};
+/// use std::time::Duration;
+///
+/// let mut list = OrderedList::new(
+///     vec!["Write the code", "Write the tests", "Ship it"],
+///     Duration::from_millis(25),
+/// );
+///
+/// list.reveal = true;
+///
+/// slide!(list).expect("each step should be numbered and revealed after an interaction");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`OrderedList`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderedList<T: Display> {
+    /// The items of the list.
+    pub items: Vec<T>,
+    /// How each item is typed.
+    pub typing: Typing,
+    /// Whether each item after the first waits for an interaction before it's typed.
+    pub reveal: bool,
+}
+
+impl<T: Display> OrderedList<T> {
+    /// Creates a list that's typed with the given delay between characters, all at once.
+    pub fn new(items: Vec<T>, typing: impl Into<Typing>) -> Self {
+        Self {
+            items,
+            typing: typing.into(),
+            reveal: false,
+        }
+    }
+}
+
+impl<T: Display> Command for OrderedList<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let (padding, width) = content_area();
+        let number_width = self.items.len().to_string().len();
+        let indent = u16::try_from(number_width + 2).unwrap_or(u16::MAX);
+        // The items are already laid out, so they shouldn't be wrapped and centered again while typing.
+        with_content_width(None, || {
+            self.items.iter().enumerate().try_for_each(|(index, item)| {
+                if index > 0 && self.reveal {
+                    WaitForInteraction.write_ansi(f)?;
+                }
+
+                let item = layout::wrap(
+                    &text::normalize_newlines(&item.to_string()),
+                    width.saturating_sub(indent) as usize,
+                    0,
+                );
+
+                MoveToColumn(padding).write_ansi(f)?;
+                typewrite(
+                    f,
+                    &format!("{:>number_width$}. ", index + 1),
+                    None,
+                    self.typing,
+                )?;
+
+                for (line_index, line) in item.split('\n').enumerate() {
+                    if line_index > 0 {
+                        text::write_newline(f)?;
+                        MoveToColumn(padding + indent).write_ansi(f)?;
+                    }
+
+                    typewrite(f, line.trim_end(), None, self.typing)?;
+                }
+
+                text::write_newline(f)
+            })
+        })
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute OrderedList command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
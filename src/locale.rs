@@ -0,0 +1,65 @@
+//! Translations of slide text, selected at runtime.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::RwLock;
+
+static LOCALES: RwLock<BTreeMap<String, HashMap<String, String>>> = RwLock::new(BTreeMap::new());
+static LOCALE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Registers the translations of a locale, replacing any that were registered under the same name.
+///
+/// Each translation maps a key, as passed to [`tr`], to the text for that locale.
+pub fn add_locale(name: impl Into<String>, translations: HashMap<String, String>) {
+    LOCALES
+        .write()
+        .expect("locales lock should not be poisoned")
+        .insert(name.into(), translations);
+}
+
+/// Sets the locale that [`tr`] translates into.
+///
+/// The locale doesn't need to be registered with [`add_locale`] yet,
+/// but until it is, every key is left untranslated.
+pub fn set_locale(name: impl Into<String>) {
+    *LOCALE.write().expect("locale lock should not be poisoned") = Some(name.into());
+}
+
+/// Returns the locale that [`tr`] translates into, if one has been set.
+pub fn locale() -> Option<String> {
+    LOCALE
+        .read()
+        .expect("locale lock should not be poisoned")
+        .clone()
+}
+
+/// Translates `key` into the current locale.
+///
+/// If no locale is set, or the current locale has no translation for `key`, `key` itself is returned,
+/// so a missing translation shows up on the slide rather than being silently skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{add_locale, set_locale, slide, tr, TypewriterPrint};
+/// use std::collections::HashMap;
+/// use std::time::Duration;
+///
+/// add_locale("en", HashMap::from([("welcome".to_string(), "Welcome!".to_string())]));
+/// add_locale("fr", HashMap::from([("welcome".to_string(), "Bienvenue !".to_string())]));
+/// set_locale(std::env::var("LANGUAGE").unwrap_or_else(|_| "en".to_string()));
+///
+/// slide!(TypewriterPrint(tr("welcome"), Duration::from_millis(25)))
+///     .expect("the welcome slide should appear in the chosen language");
+/// ```
+pub fn tr(key: &str) -> String {
+    let Some(locale) = locale() else {
+        return key.to_string();
+    };
+
+    LOCALES
+        .read()
+        .expect("locales lock should not be poisoned")
+        .get(&locale)
+        .and_then(|translations| translations.get(key))
+        .map_or_else(|| key.to_string(), Clone::clone)
+}
@@ -0,0 +1,207 @@
+//! Markdown in typewriter text, and presentations written in markdown.
+
+use crate::typing::typewrite;
+use crate::Typing;
+use crossterm::style::{Attribute, Color, SetAttribute, SetForegroundColor};
+use crossterm::Command;
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+/// The color that [`TypewriterMarkdown`] types code spans in.
+const CODE_COLOR: Color = Color::Cyan;
+
+/// A command that types text with inline markdown styles applied to it.
+///
+/// `**bold**` spans are typed in bold, `*italic*` spans in italics and `` `code` `` spans in cyan,
+/// without the markers around them.
+/// Like in markdown, a marker only opens a span if it's followed by something other than whitespace
+/// and there's a marker to close it later on, otherwise it's typed as-is,
+/// and `\*` or `` \` `` types the marker itself.
+/// Everything else, like pause markers and the [`Typing`] options, works the same way as in
+/// [`TypewriterPrint`](crate::TypewriterPrint).
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, TypewriterMarkdown};
+/// use std::time::Duration;
+///
+/// slide!(TypewriterMarkdown(
+///     "Call `unwrap` **only** when you're *sure* it can't fail.",
+///     Duration::from_millis(25),
+/// ))
+/// .expect("the text should be typed with its inline styles");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`TypewriterMarkdown`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypewriterMarkdown<T: Display, O: Into<Typing> + Copy = Duration>(pub T, pub O);
+
+impl<T: Display, O: Into<Typing> + Copy> Command for TypewriterMarkdown<T, O> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        typewrite(f, &style_inline(&self.0.to_string())?, None, self.1.into())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute TypewriterMarkdown command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// A slide of a presentation written in markdown, see [`Presentation::from_markdown`](crate::Presentation::from_markdown).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MarkdownSlide {
+    /// The text of the first heading of the slide, if it has one.
+    pub(crate) title: Option<String>,
+    /// The text of the slide, with headings already styled and everything else left for [`TypewriterMarkdown`].
+    pub(crate) text: String,
+}
+
+/// Splits `source` into slides at each line that's just `---`, leaving out blank lines around them and empty slides.
+///
+/// Headings, lines starting with one to six `#`s and a space, are typed in bold without their `#`s.
+pub(crate) fn parse_slides(source: &str) -> Vec<MarkdownSlide> {
+    let mut slides = Vec::new();
+    let mut lines = Vec::new();
+
+    for line in source.lines().chain(["---"]) {
+        if line.trim() != "---" {
+            lines.push(line);
+            continue;
+        }
+
+        let text = lines.join("\n");
+        let text = text.trim_matches('\n');
+
+        lines.clear();
+
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        let mut title = None;
+        let text = text
+            .split('\n')
+            .map(|line| match heading(line) {
+                Some(heading) => {
+                    title.get_or_insert_with(|| heading.to_string());
+
+                    format!(
+                        "{}{heading}{}",
+                        SetAttribute(Attribute::Bold),
+                        SetAttribute(Attribute::NormalIntensity),
+                    )
+                }
+                None => line.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        slides.push(MarkdownSlide { title, text });
+    }
+
+    slides
+}
+
+/// Returns the text of `line` if it's a heading, without the `#`s before it.
+fn heading(line: &str) -> Option<&str> {
+    let text = line.trim_start_matches('#');
+    let level = line.len() - text.len();
+
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+
+    text.strip_prefix(' ').map(str::trim)
+}
+
+/// Replaces the inline markdown markers in `text` with the escape sequences of the styles they stand for.
+fn style_inline(text: &str) -> Result<String, fmt::Error> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut styled = String::with_capacity(text.len());
+    let (mut bold, mut italic, mut code) = (false, false, false);
+    let mut index = 0;
+
+    while let Some(&char) = chars.get(index) {
+        let next = chars.get(index + 1).copied();
+
+        match char {
+            '\\' if matches!(next, Some('*' | '`' | '\\')) => {
+                styled.push(next.expect("the escaped character should exist"));
+                index += 2;
+                continue;
+            }
+            '`' if code || opens(&chars, index, &['`']) => {
+                code = !code;
+                SetForegroundColor(if code { CODE_COLOR } else { Color::Reset })
+                    .write_ansi(&mut styled)?;
+            }
+            '*' if !code
+                && next == Some('*')
+                && (closes(&chars, index, bold) || opens(&chars, index, &['*', '*'])) =>
+            {
+                bold = !bold;
+                SetAttribute(if bold {
+                    Attribute::Bold
+                } else {
+                    Attribute::NormalIntensity
+                })
+                .write_ansi(&mut styled)?;
+                index += 2;
+                continue;
+            }
+            '*' if !code && (closes(&chars, index, italic) || opens(&chars, index, &['*'])) => {
+                italic = !italic;
+                SetAttribute(if italic {
+                    Attribute::Italic
+                } else {
+                    Attribute::NoItalic
+                })
+                .write_ansi(&mut styled)?;
+            }
+            _ => styled.push(char),
+        }
+
+        index += 1;
+    }
+
+    // A span can be left open when its closer ends up inside another span, like an italic one closed in a code span,
+    // so it's closed at the end to keep its style from leaking into the commands after it.
+    if bold {
+        SetAttribute(Attribute::NormalIntensity).write_ansi(&mut styled)?;
+    }
+
+    if italic {
+        SetAttribute(Attribute::NoItalic).write_ansi(&mut styled)?;
+    }
+
+    if code {
+        SetForegroundColor(Color::Reset).write_ansi(&mut styled)?;
+    }
+
+    Ok(styled)
+}
+
+/// Returns `true` if the `marker` at `index` opens a span,
+/// because it's followed by something other than whitespace and closed later on.
+fn opens(chars: &[char], index: usize, marker: &[char]) -> bool {
+    let start = index + marker.len();
+
+    chars.get(start).is_some_and(|char| !char.is_whitespace())
+        && (start + 1..chars.len())
+            .any(|end| chars[end..].starts_with(marker) && !chars[end - 1].is_whitespace())
+}
+
+/// Returns `true` if the marker at `index` closes a span, because one is `open` and it follows something other than whitespace.
+fn closes(chars: &[char], index: usize, open: bool) -> bool {
+    open && index > 0 && !chars[index - 1].is_whitespace()
+}
@@ -0,0 +1,123 @@
+//! Inline pause markers understood by the typewriter commands.
+
+use std::sync::RwLock;
+use std::time::Duration;
+
+static MARKER_SYNTAX: RwLock<Option<MarkerSyntax>> = RwLock::new(Some(MarkerSyntax::DEFAULT));
+
+/// The syntax of the pause markers that can be embedded in typewriter text.
+///
+/// A marker is [`open`](MarkerSyntax::open), a number of milliseconds, then [`close`](MarkerSyntax::close).
+/// Markers aren't printed, the typewriter just sleeps for the given duration when it reaches one.
+/// Putting [`escape`](MarkerSyntax::escape) right before `open` prints `open` literally instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// slide!(TypewriterPrint(
+///     "First point{{pause:500}}then second, and a literal \\{{pause:500}}",
+///     Duration::from_millis(25),
+/// ))
+/// .expect("the slide should pause for 500ms after \"First point\"");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarkerSyntax {
+    /// The text that starts a marker.
+    pub open: &'static str,
+    /// The text that ends a marker.
+    pub close: &'static str,
+    /// The character that makes an `open` sequence print literally.
+    pub escape: char,
+}
+
+impl MarkerSyntax {
+    /// The default syntax, `{{pause:<milliseconds>}}`, escaped with `\`.
+    pub const DEFAULT: Self = Self {
+        open: "{{pause:",
+        close: "}}",
+        escape: '\\',
+    };
+}
+
+impl Default for MarkerSyntax {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Sets the syntax of the pause markers used by all typewriter commands.
+///
+/// Passing [`None`] disables pause markers, so text is always printed as-is.
+pub fn set_marker_syntax(syntax: Option<MarkerSyntax>) {
+    *MARKER_SYNTAX
+        .write()
+        .expect("marker syntax lock should not be poisoned") = syntax;
+}
+
+/// Returns the syntax of the pause markers used by all typewriter commands, if they're enabled.
+pub fn marker_syntax() -> Option<MarkerSyntax> {
+    *MARKER_SYNTAX
+        .read()
+        .expect("marker syntax lock should not be poisoned")
+}
+
+/// A piece of typewriter text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Segment {
+    /// Text that's typed one character at a time.
+    Text(String),
+    /// A pause between characters.
+    Pause(Duration),
+    /// Text or escape sequences that are written all at once.
+    Raw(String),
+}
+
+/// Splits `text` into printable text and pauses according to the current [`MarkerSyntax`].
+pub(crate) fn parse(text: &str) -> Vec<Segment> {
+    let Some(syntax) = marker_syntax() else {
+        return vec![Segment::Text(text.to_string())];
+    };
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        if let Some(after_escape) = rest.strip_prefix(syntax.escape) {
+            if let Some(after_open) = after_escape.strip_prefix(syntax.open) {
+                current.push_str(syntax.open);
+                rest = after_open;
+
+                continue;
+            }
+        }
+
+        if let Some(after_open) = rest.strip_prefix(syntax.open) {
+            if let Some((millis, after_close)) = after_open.split_once(syntax.close) {
+                if let Ok(millis) = millis.trim().parse() {
+                    if !current.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut current)));
+                    }
+
+                    segments.push(Segment::Pause(Duration::from_millis(millis)));
+                    rest = after_close;
+
+                    continue;
+                }
+            }
+        }
+
+        let char = rest.chars().next().expect("rest should not be empty");
+
+        current.push(char);
+        rest = &rest[char.len_utf8()..];
+    }
+
+    if !current.is_empty() {
+        segments.push(Segment::Text(current));
+    }
+
+    segments
+}
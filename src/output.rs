@@ -0,0 +1,395 @@
+//! Where slides are written, and rendering them without a terminal.
+
+use crate::session;
+use crossterm::{cursor, terminal};
+use std::cell::{Cell, RefCell};
+use std::io::{self, stdout, IsTerminal, Write};
+use std::rc::Rc;
+
+thread_local! {
+    static TARGET: RefCell<Option<Box<dyn Write>>> = RefCell::new(None);
+    static TARGET_IS_TERMINAL: Cell<Option<bool>> = const { Cell::new(None) };
+    static HEADLESS: Cell<bool> = const { Cell::new(false) };
+    static BATCHING: Cell<bool> = const { Cell::new(false) };
+    static BATCH: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+    static LAST_BYTE: Cell<u8> = const { Cell::new(0) };
+    static DISCARDED: Cell<Option<usize>> = const { Cell::new(None) };
+    static RECORDING: RefCell<Option<Vec<u8>>> = const { RefCell::new(None) };
+    static ERROR: RefCell<Option<io::Error>> = const { RefCell::new(None) };
+}
+
+/// Sets the stream that slides are written to on the current thread.
+///
+/// Passing [`None`] goes back to standard output.
+/// This is useful for tools that reserve standard output for machine-readable output.
+/// To keep what's written in a buffer instead, use [`capture_output`].
+/// Anything held back by [`set_output_batching`] is written to the previous stream first.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{set_output, slide, TypewriterPrint};
+/// use std::io::stderr;
+/// use std::time::Duration;
+///
+/// set_output(Some(Box::new(stderr())));
+///
+/// slide!(TypewriterPrint("Hello, world!", Duration::from_millis(25)))
+///     .expect("\"Hello, world!\" should be typed to standard error");
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the writes held back by batching can't be written to the previous stream.
+pub fn set_output(target: Option<Box<dyn Write>>) -> io::Result<()> {
+    output().flush()?;
+    TARGET.with(|current| *current.borrow_mut() = target);
+
+    Ok(())
+}
+
+/// Writes slides to a stream other than standard output until it's dropped, see [`redirect`].
+#[doc(hidden)]
+pub struct Redirect {
+    previous: Option<Box<dyn Write>>,
+    previous_is_terminal: Option<bool>,
+}
+
+impl Drop for Redirect {
+    fn drop(&mut self) {
+        let _ = output().flush();
+        TARGET.with(|target| *target.borrow_mut() = self.previous.take());
+        TARGET_IS_TERMINAL.with(|is_terminal| is_terminal.set(self.previous_is_terminal));
+    }
+}
+
+/// Writes slides to `target` on the current thread until the returned guard is dropped,
+/// then goes back to the previous stream, see [`slide_to`](crate::slide_to).
+///
+/// Unlike with [`set_output`], whether raw mode is enabled by default depends on `target` being a terminal,
+/// rather than on standard output being one.
+/// Anything held back by [`set_output_batching`] is written to the previous stream first.
+#[doc(hidden)]
+pub fn redirect(target: impl Write + IsTerminal + 'static) -> Redirect {
+    let is_terminal = target.is_terminal();
+
+    flush();
+
+    Redirect {
+        previous: TARGET.with(|current| current.borrow_mut().replace(Box::new(target))),
+        previous_is_terminal: TARGET_IS_TERMINAL.with(|current| current.replace(Some(is_terminal))),
+    }
+}
+
+/// Returns `true` if slides are written to a terminal, as far as it's known.
+///
+/// Streams set with [`set_output`] can't tell, so standard output being a terminal is what counts for them.
+pub(crate) fn is_terminal() -> bool {
+    TARGET_IS_TERMINAL
+        .with(Cell::get)
+        .unwrap_or_else(|| stdout().is_terminal())
+}
+
+/// Returns the column and row of the cursor.
+///
+/// The position is requested by writing to standard output, even if slides are written elsewhere,
+/// so it isn't requested if standard output isn't a terminal while they are,
+/// since the request would end up in whatever standard output is piped to.
+pub(crate) fn cursor_position() -> io::Result<(u16, u16)> {
+    if TARGET.with(|target| target.borrow().is_some()) && !stdout().is_terminal() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the cursor position can't be requested while standard output isn't a terminal",
+        ));
+    }
+
+    cursor::position()
+}
+
+/// Sets whether output is batched on the current thread, which it isn't by default.
+///
+/// While batching, everything written to [`output`] is held back until it's flushed,
+/// which only happens at the end of a slide and at the points where it waits,
+/// like between typed characters or for an interaction.
+/// This saves a write to the terminal per line for slides that draw a lot at once,
+/// like charts and other drawings made of many commands.
+pub fn set_output_batching(enabled: bool) {
+    BATCHING.with(|batching| batching.set(enabled));
+}
+
+/// Returns whether output is batched on the current thread.
+pub fn output_batching() -> bool {
+    BATCHING.with(Cell::get)
+}
+
+/// A handle to the stream that slides are written to, which is standard output unless changed with [`set_output`].
+///
+/// The [`slide`](crate::slide) macro writes to this, and the commands flush it between characters.
+/// Writes can be held back until the next flush with [`set_output_batching`].
+/// While a slide keeps raw mode enabled, lone `\n`s are written as `\r\n`, so they still return to the first column.
+///
+/// If a command fails to do something other than writing while it's executed, like reading a key,
+/// the next write to or flush of this returns that error,
+/// so it's returned by [`execute!`](crossterm::execute) and [`slide`](crate::slide) instead of panicking.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Output;
+
+/// Returns a handle to the stream that slides are written to.
+pub fn output() -> Output {
+    Output
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(error) = take_error() {
+            return Err(error);
+        }
+
+        if let Some(discarded) = DISCARDED.with(Cell::get) {
+            DISCARDED.with(|current| current.set(Some(discarded + buf.len())));
+
+            return Ok(buf.len());
+        }
+
+        RECORDING.with(|recording| {
+            if let Some(recording) = recording.borrow_mut().as_mut() {
+                recording.extend_from_slice(buf);
+            }
+        });
+
+        if session::is_active() {
+            write_all(&return_lines(buf))?;
+        } else {
+            write_all(buf)?;
+        }
+
+        LAST_BYTE.with(|last_byte| last_byte.set(buf.last().copied().unwrap_or(last_byte.get())));
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if let Some(error) = take_error() {
+            return Err(error);
+        }
+
+        write_batch()?;
+
+        TARGET.with(|target| match target.borrow_mut().as_mut() {
+            Some(target) => target.flush(),
+            None => stdout().flush(),
+        })
+    }
+}
+
+/// Keeps `error` to be returned by the next write to or flush of [`output`] on the current thread,
+/// unless an earlier error is already being kept.
+///
+/// Commands can only return formatting errors, which can't say what went wrong,
+/// so this is how they surface the errors of everything else they do, like enabling raw mode or reading keys.
+pub(crate) fn fail(error: io::Error) {
+    ERROR.with(|current| {
+        current.borrow_mut().get_or_insert(error);
+    });
+}
+
+/// Takes the error being kept to be returned by [`output`], if there is one.
+fn take_error() -> Option<io::Error> {
+    ERROR.with(|current| current.borrow_mut().take())
+}
+
+/// Flushes [`output`], keeping the error to be returned by its next write or flush if it fails, see [`fail`].
+pub(crate) fn flush() {
+    if let Err(error) = output().flush() {
+        fail(error);
+    }
+}
+
+/// Turns every `\n` that isn't part of a `\r\n` into `\r\n`,
+/// since raw mode doesn't return to the first column on line breaks by itself.
+fn return_lines(buf: &[u8]) -> Vec<u8> {
+    let mut previous = LAST_BYTE.with(Cell::get);
+    let mut returned = Vec::with_capacity(buf.len());
+
+    for &byte in buf {
+        if byte == b'\n' && previous != b'\r' {
+            returned.push(b'\r');
+        }
+
+        returned.push(byte);
+        previous = byte;
+    }
+
+    returned
+}
+
+/// Writes all of `buf` to the target, or holds it back if output is batched.
+fn write_all(buf: &[u8]) -> io::Result<()> {
+    if output_batching() {
+        BATCH.with(|batch| batch.borrow_mut().extend_from_slice(buf));
+
+        return Ok(());
+    }
+
+    write_batch()?;
+
+    TARGET.with(|target| match target.borrow_mut().as_mut() {
+        Some(target) => target.write_all(buf),
+        None => stdout().write_all(buf),
+    })
+}
+
+/// Writes everything held back by batching to the target.
+fn write_batch() -> io::Result<()> {
+    let batch = BATCH.with(|batch| std::mem::take(&mut *batch.borrow_mut()));
+
+    if batch.is_empty() {
+        return Ok(());
+    }
+
+    TARGET.with(|target| match target.borrow_mut().as_mut() {
+        Some(target) => target.write_all(&batch),
+        None => stdout().write_all(&batch),
+    })
+}
+
+/// Runs `render` with everything written to [`output`] on the current thread kept in a buffer instead,
+/// returning what `render` returned along with the buffer.
+///
+/// Unlike [`Slide::render_to_string`](crate::Slide::render_to_string), delays and interactions still happen,
+/// so the animation is timed just like it would be in the terminal, but what's typed goes to the buffer.
+/// This is useful for testing slides, or for sending them somewhere other than a stream, like a network connection.
+/// The previous output and batched writes are restored afterward, even if `render` panics.
+///
+/// # Errors
+///
+/// Returns an error if the writes `render` left batched can't be written once it's done,
+/// for example because `render` pointed [`output`] somewhere else with [`set_output`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{capture_output, slide, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// let (result, written) =
+///     capture_output(|| slide!(TypewriterPrint("Hello, world!", Duration::from_millis(25))))
+///         .expect("the batched writes should be written to the buffer");
+///
+/// result.expect("\"Hello, world!\" should be typed to the buffer");
+/// assert!(String::from_utf8_lossy(&written).contains("Hello, world!"));
+/// ```
+pub fn capture_output<R>(render: impl FnOnce() -> R) -> io::Result<(R, Vec<u8>)> {
+    struct Capture(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    struct Restore {
+        target: Option<Box<dyn Write>>,
+        batch: Vec<u8>,
+    }
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            TARGET.with(|target| *target.borrow_mut() = self.target.take());
+            BATCH.with(|batch| *batch.borrow_mut() = std::mem::take(&mut self.batch));
+        }
+    }
+
+    let buffer = Rc::new(RefCell::new(Vec::new()));
+    let rendered = {
+        let _restore = Restore {
+            target: TARGET.with(|current| {
+                current
+                    .borrow_mut()
+                    .replace(Box::new(Capture(Rc::clone(&buffer))))
+            }),
+            batch: BATCH.with(|batch| std::mem::take(&mut *batch.borrow_mut())),
+        };
+        let rendered = render();
+
+        write_batch()?;
+
+        rendered
+    };
+    let written = buffer.take();
+
+    Ok((rendered, written))
+}
+
+/// Discards everything written to [`output`] on the current thread until [`stop_discarding`] is called.
+pub(crate) fn start_discarding() {
+    DISCARDED.with(|discarded| discarded.set(Some(0)));
+}
+
+/// Stops discarding what's written to [`output`], returning the number of bytes that were discarded.
+pub(crate) fn stop_discarding() -> usize {
+    DISCARDED.with(|discarded| discarded.take()).unwrap_or(0)
+}
+
+/// Starts keeping a copy of everything written to [`output`] on the current thread,
+/// throwing away the copy kept so far, if any.
+pub(crate) fn start_recording() {
+    RECORDING.with(|recording| *recording.borrow_mut() = Some(Vec::new()));
+}
+
+/// Stops keeping a copy of what's written to [`output`], returning what was written since it started.
+pub(crate) fn stop_recording() -> Vec<u8> {
+    RECORDING
+        .with(|recording| recording.borrow_mut().take())
+        .unwrap_or_default()
+}
+
+/// The size that the terminal is assumed to be while rendering headlessly, so the output doesn't depend on it.
+const HEADLESS_SIZE: (u16, u16) = (80, 24);
+
+/// Returns the number of columns and rows of the terminal, which is always 80 by 24 while rendering headlessly.
+pub(crate) fn terminal_size() -> io::Result<(u16, u16)> {
+    if is_headless() {
+        return Ok(HEADLESS_SIZE);
+    }
+
+    terminal::size()
+}
+
+/// Returns `true` while slides are being rendered headlessly, see [`render_headless`].
+pub(crate) fn is_headless() -> bool {
+    HEADLESS.with(Cell::get)
+}
+
+/// Runs `render` with all output going to `target`, without delays, interactions or raw mode.
+///
+/// The previous output, headless state and batched writes are restored afterward, even if `render` panics.
+pub(crate) fn render_headless<R>(target: Box<dyn Write>, render: impl FnOnce() -> R) -> R {
+    struct Restore {
+        target: Option<Box<dyn Write>>,
+        headless: bool,
+        batch: Vec<u8>,
+    }
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            TARGET.with(|target| *target.borrow_mut() = self.target.take());
+            HEADLESS.with(|headless| headless.set(self.headless));
+            BATCH.with(|batch| *batch.borrow_mut() = std::mem::take(&mut self.batch));
+        }
+    }
+
+    let _restore = Restore {
+        target: TARGET.with(|current| current.borrow_mut().replace(target)),
+        headless: HEADLESS.with(|headless| headless.replace(true)),
+        batch: BATCH.with(|batch| std::mem::take(&mut *batch.borrow_mut())),
+    };
+
+    render()
+}
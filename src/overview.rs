@@ -0,0 +1,128 @@
+//! The overview of a running [`Presentation`](crate::Presentation), for jumping between its slides.
+
+use crate::session::RawModeGuard;
+use crate::{interaction, layout, output};
+use crossterm::cursor::MoveTo;
+use crossterm::event::{self, Event, KeyCode};
+use crossterm::style::{Print, PrintStyledContent, Stylize};
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::{execute, queue};
+use std::io::Write as _;
+
+/// How many columns each slide takes up in the grid, including the space between slides.
+const CELL_WIDTH: u16 = 28;
+
+/// The rows above the grid, taken up by the heading and a blank line.
+const HEADER_ROWS: u16 = 2;
+
+/// Shows a grid of the numbers and titles of the slides with `current` selected,
+/// until one is chosen with the arrow keys and Enter or the overview is closed.
+///
+/// Returns the index of the chosen slide, or [`None`] if the overview was closed without choosing one.
+pub(crate) fn choose(titles: &[String], current: usize) -> crossterm::Result<Option<usize>> {
+    let Some(last) = titles.len().checked_sub(1) else {
+        return Ok(None);
+    };
+    let mut raw_mode = RawModeGuard::enable()?;
+
+    raw_mode.hide_cursor()?;
+
+    let mut selected = current.min(last);
+    let chosen = loop {
+        let (columns, _) = grid_size();
+
+        draw(titles, selected)?;
+
+        if let Event::Key(key) = event::read()? {
+            if let Some(error) = interaction::press_ctrl_c(key) {
+                return Err(error);
+            }
+
+            match key.code {
+                KeyCode::Left => selected = selected.saturating_sub(1),
+                KeyCode::Right => selected = (selected + 1).min(last),
+                KeyCode::Up => selected = selected.saturating_sub(columns),
+                KeyCode::Down if selected + columns <= last => selected += columns,
+                KeyCode::Home => selected = 0,
+                KeyCode::End => selected = last,
+                KeyCode::Enter | KeyCode::Char(' ') => break Some(selected),
+                KeyCode::Esc | KeyCode::Char('o' | 'q') => break None,
+                _ => {}
+            }
+        }
+    };
+
+    execute!(output::output(), Clear(ClearType::All), MoveTo(0, 0))?;
+
+    Ok(chosen)
+}
+
+/// Returns how many slides fit side by side in the grid, and how many rows of slides fit beneath its heading.
+fn grid_size() -> (usize, usize) {
+    let (columns, rows) = output::terminal_size().unwrap_or((80, 24));
+
+    (
+        usize::from((columns / CELL_WIDTH).max(1)),
+        usize::from(rows.saturating_sub(HEADER_ROWS).max(1)),
+    )
+}
+
+/// Draws the grid with `selected` highlighted, scrolled so that it's visible.
+fn draw(titles: &[String], selected: usize) -> crossterm::Result<()> {
+    let (columns, rows) = grid_size();
+    let first_row = (selected / columns).saturating_sub(rows - 1);
+    let first = first_row * columns;
+    let mut output = output::output();
+
+    queue!(
+        output,
+        Clear(ClearType::All),
+        MoveTo(0, 0),
+        Print("Overview: arrow keys to move, Enter to jump, Esc to close"),
+    )?;
+
+    for (index, title) in titles.iter().enumerate().skip(first).take(columns * rows) {
+        let label = truncate(
+            &format!("{:>3}. {title}", index + 1),
+            usize::from(CELL_WIDTH - 2),
+        );
+        let column = (index % columns) as u16 * CELL_WIDTH;
+        let row = HEADER_ROWS + (index / columns - first_row) as u16;
+
+        queue!(output, MoveTo(column, row))?;
+
+        if index == selected {
+            queue!(output, PrintStyledContent(label.reverse()))?;
+        } else {
+            queue!(output, Print(label))?;
+        }
+    }
+
+    output.flush()?;
+
+    Ok(())
+}
+
+/// Cuts `text` off so it takes up at most `width` columns, ending it with `…` if it was cut off.
+fn truncate(text: &str, width: usize) -> String {
+    if layout::width(text) <= width {
+        return text.to_string();
+    }
+
+    let mut truncated = String::new();
+    let mut used = 0;
+
+    for char in text.chars() {
+        let char_width = layout::width(char.encode_utf8(&mut [0; 4]));
+
+        if used + char_width + 1 > width {
+            break;
+        }
+
+        truncated.push(char);
+        used += char_width;
+    }
+
+    truncated.push('…');
+    truncated
+}
@@ -0,0 +1,310 @@
+//! Playing commands back asynchronously, without blocking the thread on their delays.
+//!
+//! Commands sleep through their delays while they're rendered, which would block an async executor.
+//! Instead, a command is rendered headlessly first, which skips the delays but marks where they fall in its output,
+//! then the output is written a piece at a time, awaiting a timer for each delay in between.
+
+use crate::{output, TypewriterPrint, Typing};
+use crossterm::{execute, Command};
+use std::cell::RefCell;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::fmt::Display;
+use std::future::Future;
+use std::io::{self, Write};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static RECORDING: RefCell<Option<Recording>> = const { RefCell::new(None) };
+}
+
+/// The output of a command being rendered for playback, and the delays marked in it so far.
+struct Recording {
+    written: Rc<RefCell<Vec<u8>>>,
+    delays: Vec<(usize, Duration)>,
+}
+
+/// Marks a delay at the end of what's been written so far, if a command is being rendered for playback.
+pub(crate) fn mark_delay(delay: Duration) {
+    RECORDING.with(|recording| {
+        if let Some(recording) = recording.borrow_mut().as_mut() {
+            let end = recording.written.borrow().len();
+
+            recording.delays.push((end, delay));
+        }
+    });
+}
+
+/// Renders `command` headlessly, returning what it wrote and where its delays fall in that.
+fn record(command: impl Command) -> io::Result<Recording> {
+    struct Capture(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Puts back the recording that was being made before, even if rendering the command panics.
+    struct Restore(Option<Option<Recording>>);
+
+    impl Restore {
+        fn finish(mut self) -> Option<Recording> {
+            let previous = self
+                .0
+                .take()
+                .expect("the previous recording should be kept");
+
+            RECORDING.with(|recording| recording.replace(previous))
+        }
+    }
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            if let Some(previous) = self.0.take() {
+                RECORDING.with(|recording| recording.replace(previous));
+            }
+        }
+    }
+
+    let written = Rc::new(RefCell::new(Vec::new()));
+    let restore = Restore(Some(RECORDING.with(|recording| {
+        recording.replace(Some(Recording {
+            written: Rc::clone(&written),
+            delays: Vec::new(),
+        }))
+    })));
+    let result = output::render_headless(Box::new(Capture(Rc::clone(&written))), || {
+        execute!(output::output(), command)
+    });
+    let recording = restore
+        .finish()
+        .expect("the recording should still be there");
+
+    result?;
+
+    Ok(recording)
+}
+
+/// Writes what `command` renders to `out`, awaiting a timer for each of its delays instead of sleeping through them,
+/// so it can be played from async code without blocking the executor.
+///
+/// This works with any executor, since the timers are woken by a thread of their own,
+/// which is shared by every timer and started the first time one is awaited.
+/// That thread waits with the operating system's timeouts rather than the [`sleeper`](crate::sleeper).
+/// The command is rendered headlessly (see [`Slide::render_to_string`](crate::Slide::render_to_string)) before it's played,
+/// so it's laid out for a terminal of 80 by 24.
+/// Interactions aren't awaited: waits like [`WaitForInteraction`](crate::WaitForInteraction)
+/// and [`WaitForKey`](crate::WaitForKey) pass straight away and no keys are read,
+/// so the audience can't advance what's played, and the app it's played in has to handle input itself.
+/// Line breaks are written as `\n`, so `out` should turn them into `\r\n` itself if the terminal is in raw mode.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{play_async, TypewriterPrint};
+/// use std::io::stdout;
+/// use std::time::Duration;
+///
+/// async fn greet() {
+///     play_async(TypewriterPrint("Hello, world!", Duration::from_millis(25)), &mut stdout())
+///         .await
+///         .expect("\"Hello, world!\" should be typed without blocking");
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the command fails to render, or if `out` can't be written to.
+pub async fn play_async(command: impl Command, out: &mut impl Write) -> io::Result<()> {
+    let recording = record(command)?;
+    let written = recording.written.take();
+    let mut start = 0;
+
+    for (end, delay) in recording.delays {
+        if end > start {
+            out.write_all(&written[start..end])?;
+            start = end;
+        }
+
+        if !delay.is_zero() {
+            out.flush()?;
+            Timer::new(delay).await;
+        }
+    }
+
+    out.write_all(&written[start..])?;
+    out.flush()
+}
+
+impl<T: Display, O: Into<Typing> + Copy> TypewriterPrint<T, O> {
+    /// Types the text to `out` without blocking the thread, see [`play_async`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::TypewriterPrint;
+    /// use std::io::stdout;
+    /// use std::time::Duration;
+    ///
+    /// async fn greet() {
+    ///     TypewriterPrint("Hello, world!", Duration::from_millis(25))
+    ///         .play_async(&mut stdout())
+    ///         .await
+    ///         .expect("\"Hello, world!\" should be typed without blocking");
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `out` can't be written to.
+    pub async fn play_async(&self, out: &mut impl Write) -> io::Result<()> {
+        play_async(self, out).await
+    }
+}
+
+/// A future that's ready once its duration has passed, woken by the [`timer_thread`].
+struct Timer {
+    deadline: Instant,
+    state: Option<Arc<Mutex<TimerState>>>,
+}
+
+/// What a [`Timer`] shares with the thread that wakes it.
+struct TimerState {
+    done: bool,
+    waker: Waker,
+}
+
+/// A timer waiting on the [`timer_thread`] to be woken at its deadline.
+struct Sleeping {
+    deadline: Instant,
+    state: Arc<Mutex<TimerState>>,
+}
+
+impl PartialEq for Sleeping {
+    fn eq(&self, other: &Self) -> bool {
+        self.deadline == other.deadline
+    }
+}
+
+impl Eq for Sleeping {}
+
+impl PartialOrd for Sleeping {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Sleeping {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.deadline.cmp(&other.deadline)
+    }
+}
+
+/// Returns the sender of timers to the thread that wakes them, starting the thread the first time.
+///
+/// The thread keeps the timers in a queue ordered by deadline,
+/// waiting for the earliest one or for a new timer to be sent, whichever comes first.
+fn timer_thread() -> &'static Sender<Sleeping> {
+    static SENDER: OnceLock<Sender<Sleeping>> = OnceLock::new();
+
+    SENDER.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<Sleeping>();
+
+        thread::spawn(move || {
+            let mut queue = BinaryHeap::new();
+
+            loop {
+                let received = match queue.peek() {
+                    Some(Reverse(Sleeping { deadline, .. })) => {
+                        receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                    }
+                    None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                };
+
+                match received {
+                    Ok(timer) => queue.push(Reverse(timer)),
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+
+                while queue
+                    .peek()
+                    .is_some_and(|Reverse(timer)| timer.deadline <= Instant::now())
+                {
+                    let Some(Reverse(timer)) = queue.pop() else {
+                        break;
+                    };
+                    let mut state = timer
+                        .state
+                        .lock()
+                        .expect("timer lock should not be poisoned");
+
+                    state.done = true;
+                    state.waker.wake_by_ref();
+                }
+            }
+        });
+
+        sender
+    })
+}
+
+impl Timer {
+    fn new(duration: Duration) -> Self {
+        Self {
+            deadline: Instant::now() + duration,
+            state: None,
+        }
+    }
+}
+
+impl Future for Timer {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<()> {
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(());
+        }
+
+        match &self.state {
+            Some(state) => {
+                let mut state = state.lock().expect("timer lock should not be poisoned");
+
+                if state.done {
+                    return Poll::Ready(());
+                }
+
+                state.waker.clone_from(context.waker());
+            }
+            None => {
+                let state = Arc::new(Mutex::new(TimerState {
+                    done: false,
+                    waker: context.waker().clone(),
+                }));
+                let sleeping = Sleeping {
+                    deadline: self.deadline,
+                    state: Arc::clone(&state),
+                };
+
+                // The thread only stops if the sender is dropped, which it never is since it's static.
+                let _ = timer_thread().send(sleeping);
+                self.state = Some(state);
+            }
+        }
+
+        Poll::Pending
+    }
+}
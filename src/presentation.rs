@@ -0,0 +1,1207 @@
+//! Presentations made of multiple slides.
+
+use crate::clock::MinutesSeconds;
+use crate::runner::{self, Interrupt};
+use crate::session::RawModeGuard;
+use crate::{cleanup, estimate, interaction, markdown, overview};
+use crate::{
+    layout, output, slide, text, typing, Corner, SlideProgress, TypewriterMarkdown,
+    TypewriterPrint, Typing, WaitForInteraction,
+};
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode};
+use crossterm::style::{
+    Color, ContentStyle, Print, PrintStyledContent, ResetColor, SetBackgroundColor, Stylize,
+};
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::{execute, queue};
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{self, sink, Write as _};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// A slide of a [`Presentation`], usually a closure that invokes [`slide`](crate::slide).
+type SlideFn = Box<dyn Fn() -> crossterm::Result<()>>;
+
+/// A sequence of slides that are shown one after another.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, Presentation, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// Presentation::new()
+///     .splash(Default::default())
+///     .slide(|| slide!(TypewriterPrint("Hello, world!", Duration::from_millis(25))))
+///     .slide(|| slide!(TypewriterPrint("Goodbye, world!", Duration::from_millis(25))))
+///     .run()
+///     .expect("the presentation should run");
+/// ```
+#[derive(Default)]
+pub struct Presentation {
+    slides: Vec<Entry>,
+    splash: Option<Splash>,
+    content_width: Option<u16>,
+    profiles: HashMap<String, Typing>,
+    clock: Option<Corner>,
+    progress: Option<Corner>,
+    max_slide_duration: Option<Duration>,
+    minimum_size: Option<(u16, u16)>,
+    transcript_path: Option<PathBuf>,
+    interaction_estimate: Duration,
+    frame_rate: Option<u32>,
+    current: usize,
+    step: usize,
+    stopped_early: Cell<bool>,
+}
+
+/// Code run when a slide of a [`Presentation`] is entered or exited.
+type HookFn = Box<dyn Fn() -> crossterm::Result<()>>;
+
+/// A slide of a [`Presentation`], along with the name of its profile and its hooks, if it has them.
+struct Entry {
+    render: SlideFn,
+    profile: Option<String>,
+    on_enter: Option<HookFn>,
+    on_exit: Option<HookFn>,
+    title: Option<String>,
+}
+
+impl Presentation {
+    /// Creates a presentation without any slides.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a presentation with a slide for each paragraph, which is typed with `delay` between characters.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::Presentation;
+    /// use std::time::Duration;
+    ///
+    /// Presentation::from_paragraphs(
+    ///     &["Hello, world!", "This is a quick deck.", "Goodbye, world!"],
+    ///     Duration::from_millis(25),
+    /// )
+    /// .run()
+    /// .expect("the presentation should run");
+    /// ```
+    pub fn from_paragraphs(paragraphs: &[&str], delay: Duration) -> Self {
+        paragraphs
+            .iter()
+            .map(|paragraph| paragraph.to_string())
+            .fold(Self::new(), |presentation, paragraph| {
+                presentation.slide(move || slide!(TypewriterPrint(&paragraph, delay)))
+            })
+    }
+
+    /// Creates a presentation from a talk written in markdown, typed with `delay` between characters.
+    ///
+    /// Slides are separated by lines that are just `---`, and each one is typed like [`TypewriterMarkdown`],
+    /// so `**bold**`, `*italic*` and `` `code` `` spans are styled.
+    /// Headings, lines starting with `#`s and a space, are typed in bold without their `#`s,
+    /// and the first one of each slide becomes its title in the overview (see [`Presentation::title`]).
+    /// Blank lines around each slide, and slides with nothing but blank lines, are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::Presentation;
+    /// use std::time::Duration;
+    ///
+    /// Presentation::from_markdown(
+    ///     "# Hello, world!\n\nThis is a **quick** deck.\n---\n# Goodbye, world!",
+    ///     Duration::from_millis(25),
+    /// )
+    /// .run()
+    /// .expect("the presentation should run");
+    /// ```
+    pub fn from_markdown(markdown: &str, delay: Duration) -> Self {
+        markdown::parse_slides(markdown)
+            .into_iter()
+            .fold(Self::new(), |presentation, parsed| {
+                let text = parsed.text;
+                let presentation =
+                    presentation.slide(move || slide!(TypewriterMarkdown(&text, delay)));
+
+                match parsed.title {
+                    Some(title) => presentation.title(title),
+                    None => presentation,
+                }
+            })
+    }
+
+    /// Creates a presentation from the talk written in markdown at `path`, see [`Presentation::from_markdown`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::Presentation;
+    /// use std::time::Duration;
+    ///
+    /// Presentation::from_markdown_file("talk.md", Duration::from_millis(25))
+    ///     .expect("the talk should be read")
+    ///     .run()
+    ///     .expect("the presentation should run");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read.
+    pub fn from_markdown_file(path: impl AsRef<Path>, delay: Duration) -> io::Result<Self> {
+        Ok(Self::from_markdown(&fs::read_to_string(path)?, delay))
+    }
+
+    /// Adds a slide to the end of the presentation.
+    pub fn slide(mut self, slide: impl Fn() -> crossterm::Result<()> + 'static) -> Self {
+        self.slides.push(Entry {
+            render: Box::new(slide),
+            profile: None,
+            on_enter: None,
+            on_exit: None,
+            title: None,
+        });
+        self
+    }
+
+    /// Adds a slide to the end of the presentation that uses the profile named `profile`.
+    ///
+    /// The typewriter commands of that slide take any [`Typing`] options they don't set from the profile.
+    /// The profile must be registered with [`Presentation::profile`] before the presentation is run.
+    pub fn slide_with_profile(
+        mut self,
+        profile: impl Into<String>,
+        slide: impl Fn() -> crossterm::Result<()> + 'static,
+    ) -> Self {
+        self.slides.push(Entry {
+            render: Box::new(slide),
+            profile: Some(profile.into()),
+            on_enter: None,
+            on_exit: None,
+            title: None,
+        });
+        self
+    }
+
+    /// Runs `hook` whenever the last slide added so far becomes active, before it's shown.
+    ///
+    /// This is useful for integrating a presentation with the rest of a live production,
+    /// like starting a timer or switching a scene.
+    /// Hooks run on the thread running the presentation, so the slide isn't shown until the hook returns.
+    /// If the hook returns an error, the presentation stops and [`Presentation::run`] returns it.
+    /// Hooks only run while the presentation is [run](Presentation::run),
+    /// not when it's [validated](Presentation::validate) or [rendered manually](Presentation::render_current).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, Presentation, TypewriterPrint};
+    /// use std::time::Duration;
+    ///
+    /// Presentation::new()
+    ///     .slide(|| slide!(TypewriterPrint("Here's the demo!", Duration::from_millis(25))))
+    ///     .on_enter(|| {
+    ///         eprintln!("switch to the demo scene");
+    ///         Ok(())
+    ///     })
+    ///     .on_exit(|| {
+    ///         eprintln!("switch back to the slides scene");
+    ///         Ok(())
+    ///     })
+    ///     .run()
+    ///     .expect("the presentation should run");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if no slide has been added yet.
+    pub fn on_enter(mut self, hook: impl Fn() -> crossterm::Result<()> + 'static) -> Self {
+        self.slides
+            .last_mut()
+            .expect("a slide should be added before its hooks")
+            .on_enter = Some(Box::new(hook));
+        self
+    }
+
+    /// Runs `hook` whenever the last slide added so far is left, either for the next one after it's done
+    /// or for another one chosen in the overview (see [`Presentation::title`]).
+    ///
+    /// This works just like [`Presentation::on_enter`], including how errors are handled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no slide has been added yet.
+    pub fn on_exit(mut self, hook: impl Fn() -> crossterm::Result<()> + 'static) -> Self {
+        self.slides
+            .last_mut()
+            .expect("a slide should be added before its hooks")
+            .on_exit = Some(Box::new(hook));
+        self
+    }
+
+    /// Sets the title of the last slide added so far, which is shown for it in the overview.
+    ///
+    /// While a presentation is running, pressing `o` shows an overview of all of its slides,
+    /// where the arrow keys and Enter jump to any one of them.
+    /// Slides without a title are shown with the first line of text they print instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no slide has been added yet.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.slides
+            .last_mut()
+            .expect("a slide should be added before its title")
+            .title = Some(title.into());
+        self
+    }
+
+    /// Registers a named profile, a set of [`Typing`] options that slides can share.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, Presentation, TypewriterPrint, Typing};
+    /// use std::time::Duration;
+    ///
+    /// Presentation::new()
+    ///     .profile(
+    ///         "dramatic",
+    ///         Typing::new(Duration::from_millis(80)).with_punctuation_pause(4.0, 10.0),
+    ///     )
+    ///     .slide_with_profile("dramatic", || {
+    ///         slide!(TypewriterPrint("And then... it happened.", Typing::inherit()))
+    ///     })
+    ///     .run()
+    ///     .expect("the presentation should run");
+    /// ```
+    pub fn profile(mut self, name: impl Into<String>, typing: Typing) -> Self {
+        self.profiles.insert(name.into(), typing);
+        self
+    }
+
+    /// Shows the given [`Splash`] and waits for an interaction before the first slide.
+    pub fn splash(mut self, splash: Splash) -> Self {
+        self.splash = Some(splash);
+        self
+    }
+
+    /// Word-wraps all typewriter output to `width` columns, centered horizontally in the terminal.
+    ///
+    /// This makes slides look the same regardless of how wide the terminal is.
+    /// If the terminal is narrower than `width`, the text is wrapped to `width` columns without any padding.
+    pub fn content_width(mut self, width: u16) -> Self {
+        self.content_width = Some(width);
+        self
+    }
+
+    /// Shows the time elapsed since the presentation started in `corner`, like `07:42`,
+    /// which is kept up to date while waiting for interactions.
+    ///
+    /// To print the elapsed time as part of a slide instead, use [`ElapsedTime`](crate::ElapsedTime).
+    pub fn elapsed_time(mut self, corner: Corner) -> Self {
+        self.clock = Some(corner);
+        self
+    }
+
+    /// Shows how far through the presentation the current slide is in `corner`, like `3 / 12`,
+    /// while waiting for interactions.
+    ///
+    /// To print the progress as part of a slide instead, use [`SlideProgress`](crate::SlideProgress).
+    pub fn slide_progress(mut self, corner: Corner) -> Self {
+        self.progress = Some(corner);
+        self
+    }
+
+    /// Advances slides that have been shown for longer than `duration`, to help keep a talk on time.
+    ///
+    /// Once a slide has been shown for `duration`, a warning appears at the bottom of it while it waits,
+    /// counting down ten seconds until it advances by itself,
+    /// which can be avoided by advancing before the countdown ends.
+    pub fn max_slide_duration(mut self, duration: Duration) -> Self {
+        self.max_slide_duration = Some(duration);
+        self
+    }
+
+    /// Requires the terminal to be at least `columns` wide and `rows` tall for the slides to be shown.
+    ///
+    /// Before each slide, if the terminal is smaller than that,
+    /// a message saying so is shown until the terminal is resized to fit, instead of slides with broken layouts.
+    /// Meanwhile, the quit keys stop the presentation, and Ctrl+C does too (see [`set_ctrl_c`](crate::set_ctrl_c)).
+    pub fn minimum_size(mut self, columns: u16, rows: u16) -> Self {
+        self.minimum_size = Some((columns, rows));
+        self
+    }
+
+    /// Writes the text of every slide that was shown to the file at `path` once the presentation is done,
+    /// in the order of the presentation, with a line like `--- Slide 1 ---` before each one.
+    ///
+    /// A slide that's shown more than once, like after going back to it, is only written once,
+    /// as it was shown the last time.
+    /// This is useful for keeping a record of a talk, to turn into notes after the fact.
+    /// Only the text is written, without any styles or cursor movements.
+    /// The file is written even if the presentation stops early because of an error or a panic,
+    /// in which case the slide it stopped on is written as far as it got.
+    pub fn transcript_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.transcript_path = Some(path.into());
+        self
+    }
+
+    /// Sets how long each interaction is assumed to take when estimating how long the presentation takes,
+    /// which is no time at all by default.
+    ///
+    /// This is the time spent talking before moving on, so it's usually the bulk of a talk.
+    pub fn interaction_estimate(mut self, duration: Duration) -> Self {
+        self.interaction_estimate = duration;
+        self
+    }
+
+    /// Estimates how long each slide takes to present, by adding up its delays
+    /// and the [time assumed for each of its interactions](Presentation::interaction_estimate).
+    ///
+    /// The slides are rendered without delays, interactions or output to measure them,
+    /// so this takes next to no time.
+    /// Slides that fail to render (see [`Presentation::validate`]) are estimated as far as they got.
+    pub fn slide_estimates(&self) -> Vec<Duration> {
+        let previous_content_width = layout::set_content_width(self.content_width);
+        let estimates = self
+            .slides
+            .iter()
+            .map(|slide| self.estimate(slide))
+            .collect();
+
+        layout::set_content_width(previous_content_width);
+
+        estimates
+    }
+
+    /// Estimates how long the whole presentation takes, which is the total of its [`Presentation::slide_estimates`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, Presentation, TypewriterPrint};
+    /// use std::time::Duration;
+    ///
+    /// let presentation = Presentation::new()
+    ///     .interaction_estimate(Duration::from_secs(45))
+    ///     .slide(|| slide!(TypewriterPrint("Hello, world!", Duration::from_millis(25))))
+    ///     .slide(|| slide!(TypewriterPrint("Goodbye, world!", Duration::from_millis(25))));
+    ///
+    /// eprintln!("{}", presentation.estimate_summary());
+    ///
+    /// if presentation.estimated_total_duration() > Duration::from_secs(20 * 60) {
+    ///     eprintln!("the talk won't fit in its twenty minute slot");
+    /// }
+    /// ```
+    pub fn estimated_total_duration(&self) -> Duration {
+        self.slide_estimates()
+            .into_iter()
+            .fold(Duration::ZERO, Duration::saturating_add)
+    }
+
+    /// Returns a table of the [estimates](Presentation::slide_estimates) of each slide along with their titles
+    /// (see [`Presentation::title`]), followed by the total.
+    pub fn estimate_summary(&self) -> String {
+        let estimates = self.slide_estimates();
+        let mut summary = String::new();
+
+        for ((index, slide), estimate) in self.slides.iter().enumerate().zip(&estimates) {
+            summary.push_str(&format!(
+                "{:>3}. {:<40} {}\n",
+                index + 1,
+                self.title_of(slide).chars().take(40).collect::<String>(),
+                MinutesSeconds(*estimate),
+            ));
+        }
+
+        summary.push_str(&format!(
+            "{:<45} {}",
+            "Total",
+            MinutesSeconds(
+                estimates
+                    .into_iter()
+                    .fold(Duration::ZERO, Duration::saturating_add)
+            ),
+        ));
+        summary
+    }
+
+    /// Caps how many times per second typed characters are written to the terminal at `frame_rate`,
+    /// by writing the characters typed within each frame all at once.
+    ///
+    /// Typing takes just as long as it would otherwise, so it looks the same to the audience,
+    /// but recordings, like those made with asciinema, have far fewer and larger updates,
+    /// which makes for smaller files.
+    /// This sets the [flush interval](Typing::with_flush_interval) of every slide,
+    /// unless its profile or typewriter command sets one itself.
+    /// A frame rate of zero leaves the output uncapped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, Presentation, TypewriterPrint};
+    /// use std::time::Duration;
+    ///
+    /// Presentation::new()
+    ///     .frame_rate(30)
+    ///     .slide(|| slide!(TypewriterPrint("Hello, world!", Duration::from_millis(5))))
+    ///     .run()
+    ///     .expect("the presentation should run at 30 frames per second");
+    /// ```
+    pub fn frame_rate(mut self, frame_rate: u32) -> Self {
+        self.frame_rate = Some(frame_rate);
+        self
+    }
+
+    /// Returns the number of slides in the presentation.
+    pub fn len(&self) -> usize {
+        self.slides.len()
+    }
+
+    /// Returns `true` if the presentation has no slides.
+    pub fn is_empty(&self) -> bool {
+        self.slides.is_empty()
+    }
+
+    /// Returns the index of the slide that [`Presentation::render_current`] shows, starting at `0`.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Moves to the next step of the current slide, or to the next slide if the current one is done,
+    /// returning `false` if there's nowhere to move to.
+    ///
+    /// Together with [`Presentation::back`] and [`Presentation::render_current`],
+    /// this lets an application with its own event loop drive the presentation,
+    /// so it's shown without reading any input.
+    /// A slide has a step for every wait it stops at, like [`WaitForInteraction`],
+    /// and whether it's done is only known once its last step has been rendered.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, Presentation, TypewriterPrint, WaitForInteraction};
+    /// use std::time::Duration;
+    ///
+    /// let mut presentation = Presentation::new()
+    ///     .slide(|| {
+    ///         slide!(
+    ///             TypewriterPrint("First point.\n", Duration::from_millis(25)),
+    ///             WaitForInteraction,
+    ///             TypewriterPrint("Second point.", Duration::from_millis(25)),
+    ///         )
+    ///     })
+    ///     .slide(|| slide!(TypewriterPrint("Goodbye, world!", Duration::from_millis(25))));
+    ///
+    /// presentation.render_current().expect("the first point should appear");
+    ///
+    /// // Whenever the application's own input says to move on...
+    /// while presentation.advance() {
+    ///     presentation.render_current().expect("the next step should appear");
+    /// }
+    /// ```
+    pub fn advance(&mut self) -> bool {
+        if self.stopped_early.replace(false) {
+            self.step += 1;
+        } else if self.current + 1 < self.slides.len() {
+            self.current += 1;
+            self.step = 0;
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    /// Moves back to the start of the previous slide, returning `false` if the current slide is the first one.
+    pub fn back(&mut self) -> bool {
+        if self.current == 0 {
+            return false;
+        }
+
+        self.current -= 1;
+        self.step = 0;
+        self.stopped_early.set(false);
+
+        true
+    }
+
+    /// Renders the current step of the current slide, without reading any input.
+    ///
+    /// The steps before it are rendered instantly, then the slide is rendered up until its next wait,
+    /// which it stops at instead of waiting.
+    /// The elapsed time and maximum slide duration aren't shown, since they only apply while waiting.
+    /// If the terminal is smaller than the [minimum size](Presentation::minimum_size),
+    /// a message saying so is shown instead, so the slide should be rendered again once the terminal is resized.
+    pub fn render_current(&self) -> crossterm::Result<()> {
+        let Some(slide) = self.slides.get(self.current) else {
+            return Ok(());
+        };
+
+        if let Some((columns, rows)) = self.minimum_size {
+            let mut message = String::new();
+
+            if layout::write_too_small(&mut message, columns, rows)
+                .expect("the message should be written to a string")
+            {
+                return execute!(
+                    output::output(),
+                    Clear(ClearType::All),
+                    MoveTo(0, 0),
+                    Print(message)
+                );
+            }
+        }
+
+        let previous_content_width = layout::set_content_width(self.content_width);
+        let _manual = runner::start_manual();
+
+        runner::begin_slide(self.step);
+
+        let result = self.show(slide);
+        let discarded = output::stop_discarding();
+
+        self.stopped_early
+            .set(runner::is_stopped() && discarded > 0);
+        layout::set_content_width(previous_content_width);
+
+        result
+    }
+
+    /// Shows every slide of the presentation, in order.
+    ///
+    /// Left and Backspace undo the last interaction of the current slide,
+    /// or go back to the previous slide and show it again from the start if there's nothing to undo.
+    /// The keys understood while it runs are listed in the help overlay, shown with `?` or `h`.
+    /// Whenever the terminal is resized while waiting for an interaction,
+    /// the current slide is shown again up to that interaction, without delays, laid out for the new size.
+    /// A quit key pressed on the [splash screen](Presentation::splash) stops the presentation before its first slide.
+    pub fn run(&self) -> crossterm::Result<()> {
+        if let Some(splash) = &self.splash {
+            interaction::set_quit_requested(false);
+            splash.show()?;
+
+            // The splash is shown before the presentation runs, so a quit key only asks to stop.
+            if interaction::quit_requested() {
+                return Ok(());
+            }
+        }
+
+        let previous_content_width = layout::set_content_width(self.content_width);
+        let mut transcript = Transcript::new(self.transcript_path.clone());
+        let result = self.present(&mut transcript, None).map(|_| ());
+        let written = transcript.write();
+
+        layout::set_content_width(previous_content_width);
+
+        result.and(written)
+    }
+
+    /// Shows every slide of the presentation, in order, over and over until `q` or Escape is pressed,
+    /// which is useful for unattended booths and kiosks.
+    ///
+    /// Every wait passes by itself after `advance_after`, including the one at the end of each slide,
+    /// and after the last slide, the presentation waits for `advance_after` again before starting over.
+    /// Keys passing waits still work, but the other keys of a running presentation, like the overview, don't.
+    /// The splash screen, the maximum slide duration and the transcript are left out,
+    /// and the terminal is reset once the loop stops (see [`reset_terminal`](crate::reset_terminal)).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, Presentation, TypewriterPrint};
+    /// use std::time::Duration;
+    ///
+    /// Presentation::new()
+    ///     .slide(|| slide!(TypewriterPrint("Welcome to our booth!", Duration::from_millis(25))))
+    ///     .slide(|| slide!(TypewriterPrint("Ask us anything.", Duration::from_millis(25))))
+    ///     .run_loop(Duration::from_secs(5))
+    ///     .expect("the presentation should loop until q is pressed");
+    /// ```
+    pub fn run_loop(&self, advance_after: Duration) -> crossterm::Result<()> {
+        if self.slides.is_empty() {
+            return Ok(());
+        }
+
+        let previous_content_width = layout::set_content_width(self.content_width);
+        let result = (|| {
+            while self.present(&mut Transcript::new(None), Some(advance_after))? {
+                if wait_for_quit(advance_after)? {
+                    break;
+                }
+
+                execute!(output::output(), Clear(ClearType::All), MoveTo(0, 0))?;
+            }
+
+            Ok(())
+        })();
+
+        layout::set_content_width(previous_content_width);
+
+        result.and(cleanup::reset_terminal())
+    }
+
+    /// Shows every slide, handling interrupts, until the last one is done,
+    /// returning `false` if the presentation was stopped before that.
+    ///
+    /// If `advance_after` is set, the presentation loops unattended, see [`Presentation::run_loop`].
+    fn present(
+        &self,
+        transcript: &mut Transcript,
+        advance_after: Option<Duration>,
+    ) -> crossterm::Result<bool> {
+        let _running = match advance_after {
+            Some(advance_after) => runner::start_loop(self.clock, advance_after),
+            None => runner::start(self.clock, self.max_slide_duration),
+        };
+
+        interaction::set_quit_requested(false);
+
+        let mut index = 0;
+        let mut fast_forward = 0;
+        let mut origin = (0, 0);
+        let mut timed = None;
+        let mut catch_up = false;
+
+        while let Some(slide) = self.slides.get(index) {
+            if timed != Some(index) {
+                if let Some((columns, rows)) = self.minimum_size {
+                    if !wait_for_size(columns, rows)? {
+                        return Ok(false);
+                    }
+                }
+
+                if let Some(on_enter) = &slide.on_enter {
+                    on_enter()?;
+                }
+
+                runner::start_slide_timer();
+                timed = Some(index);
+            }
+
+            if fast_forward == 0 {
+                origin = output::cursor_position().unwrap_or((0, 0));
+            }
+
+            runner::begin_slide(fast_forward);
+            runner::set_progress(self.progress.map(|corner| SlideProgress {
+                current: index + 1,
+                total: self.slides.len(),
+                corner,
+            }));
+
+            if catch_up {
+                runner::catch_up();
+                catch_up = false;
+            }
+
+            transcript.start_slide(index);
+            self.show(slide)?;
+
+            fast_forward = 0;
+
+            match runner::take_interrupt() {
+                None => {
+                    transcript.finish_slide();
+
+                    if let Some(on_exit) = &slide.on_exit {
+                        on_exit()?;
+                    }
+
+                    index += 1;
+                }
+                Some(interrupt) => {
+                    fast_forward = runner::waits_passed();
+                    catch_up = true;
+
+                    match interrupt {
+                        Interrupt::Quit => return Ok(false),
+                        Interrupt::Help => show_help()?,
+                        Interrupt::BlackScreen => show_black_screen()?,
+                        Interrupt::Overview => {
+                            let titles: Vec<String> = self
+                                .slides
+                                .iter()
+                                .map(|slide| self.title_of(slide))
+                                .collect();
+
+                            if let Some(chosen) = overview::choose(&titles, index)? {
+                                if chosen != index {
+                                    if let Some(on_exit) = &slide.on_exit {
+                                        on_exit()?;
+                                    }
+
+                                    index = chosen;
+                                    fast_forward = 0;
+                                    catch_up = false;
+                                    continue;
+                                }
+                            }
+                        }
+                        // Going back from the start of a slide shows the previous one again from scratch,
+                        // since what it wrote isn't kept anywhere.
+                        Interrupt::StepBack if fast_forward == 0 && index > 0 => {
+                            if let Some(on_exit) = &slide.on_exit {
+                                on_exit()?;
+                            }
+
+                            index -= 1;
+                            catch_up = false;
+                            continue;
+                        }
+                        Interrupt::Resize => {
+                            execute!(output::output(), Clear(ClearType::All))?;
+
+                            if let Some((columns, rows)) = self.minimum_size {
+                                if !wait_for_size(columns, rows)? {
+                                    return Ok(false);
+                                }
+                            }
+                        }
+                        Interrupt::StepBack => fast_forward = fast_forward.saturating_sub(1),
+                    }
+
+                    // Slides shown with `clear: false;` don't clear the screen themselves,
+                    // so whatever was drawn over them, like the help overlay, has to be cleared for them.
+                    execute!(
+                        output::output(),
+                        MoveTo(origin.0, origin.1),
+                        Clear(ClearType::FromCursorDown),
+                    )?;
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Renders every slide without delays, interactions or output, and returns the slides that failed.
+    ///
+    /// A slide fails if it returns an error or panics.
+    /// This is useful for checking that a presentation won't break partway through before presenting it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, Presentation, TypewriterPrint};
+    /// use std::time::Duration;
+    ///
+    /// let presentation = Presentation::new()
+    ///     .slide(|| slide!(TypewriterPrint("Hello, world!", Duration::from_millis(25))));
+    ///
+    /// for failure in presentation.validate() {
+    ///     eprintln!("slide {} failed: {}", failure.index + 1, failure.message);
+    /// }
+    /// ```
+    pub fn validate(&self) -> Vec<SlideFailure> {
+        let previous_content_width = layout::set_content_width(self.content_width);
+        let failures = self
+            .slides
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slide)| {
+                let result = output::render_headless(Box::new(sink()), || {
+                    panic::catch_unwind(AssertUnwindSafe(|| self.show(slide)))
+                });
+                let message = match result {
+                    Ok(Ok(())) => return None,
+                    Ok(Err(error)) => error.to_string(),
+                    Err(payload) => match payload.downcast::<String>() {
+                        Ok(message) => *message,
+                        Err(payload) => match payload.downcast::<&str>() {
+                            Ok(message) => message.to_string(),
+                            Err(_) => "the slide panicked".to_string(),
+                        },
+                    },
+                };
+
+                Some(SlideFailure { index, message })
+            })
+            .collect();
+
+        layout::set_content_width(previous_content_width);
+
+        failures
+    }
+
+    /// Estimates how long a single slide takes to present.
+    fn estimate(&self, slide: &Entry) -> Duration {
+        let estimate = estimate::measure(|| {
+            let _ = output::render_headless(Box::new(sink()), || self.show(slide));
+        });
+        let interactions = u32::try_from(estimate.interactions).unwrap_or(u32::MAX);
+
+        estimate.delays.saturating_add(
+            self.interaction_estimate
+                .checked_mul(interactions)
+                .unwrap_or(Duration::MAX),
+        )
+    }
+
+    /// Returns the title of a slide, which is the first line of text it prints if it wasn't given one.
+    fn title_of(&self, slide: &Entry) -> String {
+        if let Some(title) = &slide.title {
+            return title.clone();
+        }
+
+        crate::slide::render_to_string(&|| self.show(slide), false)
+            .ok()
+            .and_then(|rendered| {
+                rendered
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty())
+                    .map(str::to_string)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Shows a single slide with its profile applied.
+    fn show(&self, slide: &Entry) -> crossterm::Result<()> {
+        let profile = match &slide.profile {
+            Some(name) => Some(*self.profiles.get(name).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("no profile named \"{name}\" is registered"),
+                )
+            })?),
+            None => None,
+        };
+        let profile =
+            match self.frame_rate {
+                Some(frame_rate) if frame_rate > 0 => Some(profile.unwrap_or_default().or(
+                    Typing::inherit().with_flush_interval(Duration::from_secs(1) / frame_rate),
+                )),
+                _ => profile,
+            };
+        let previous_profile = typing::set_profile(profile);
+        let result = (slide.render)();
+
+        typing::set_profile(previous_profile);
+
+        result
+    }
+}
+
+/// The text of the slides shown by a running presentation, which is written to a file once it's done.
+struct Transcript {
+    path: Option<PathBuf>,
+    slides: BTreeMap<usize, String>,
+    current: usize,
+    written: bool,
+}
+
+impl Transcript {
+    /// Creates a transcript that's written to `path`, or one that isn't kept at all if there's no path.
+    fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            slides: BTreeMap::new(),
+            current: 0,
+            written: false,
+        }
+    }
+
+    /// Starts recording the slide at `index`, throwing away what was recorded of any previous attempt at showing it.
+    fn start_slide(&mut self, index: usize) {
+        self.current = index;
+
+        if self.path.is_some() {
+            output::start_recording();
+        }
+    }
+
+    /// Keeps the text of the slide that was recorded, in place of what it showed any previous time.
+    fn finish_slide(&mut self) {
+        if self.path.is_some() {
+            self.slides
+                .insert(self.current, plain_text(&output::stop_recording()));
+        }
+    }
+
+    /// Writes the transcript to its file, including the slide being recorded as far as it got.
+    fn write(&mut self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        self.written = true;
+
+        let partial = output::stop_recording();
+
+        if !partial.is_empty() {
+            self.slides.insert(self.current, plain_text(&partial));
+        }
+
+        let mut transcript = String::new();
+
+        for (index, slide) in &self.slides {
+            if !transcript.is_empty() {
+                transcript.push_str("\n\n");
+            }
+
+            transcript.push_str(&format!(
+                "--- Slide {} ---\n{}",
+                index + 1,
+                slide.trim_end()
+            ));
+        }
+
+        transcript.push('\n');
+        fs::write(path, transcript)
+    }
+}
+
+/// Returns the text of what was written to the output, without escape sequences or carriage returns.
+fn plain_text(written: &[u8]) -> String {
+    text::strip_escapes(&String::from_utf8_lossy(written)).replace('\r', "")
+}
+
+impl Drop for Transcript {
+    fn drop(&mut self) {
+        // Presentations that panic don't get to write their transcript, so it's written here instead.
+        if !self.written {
+            let _ = self.write();
+        }
+    }
+}
+
+/// The keys understood by a running presentation other than the advance and quit keys, and what they do.
+const HELP: &[(&str, &str)] = &[
+    ("Left, Backspace", "Undo the last step, or go back a slide"),
+    ("b", "Black screen, until any key is pressed"),
+    ("o", "Overview"),
+    ("?, h", "Show this help"),
+];
+
+/// Draws an overlay listing the keys understood by a running presentation,
+/// then waits for any key to be pressed.
+fn show_help() -> crossterm::Result<()> {
+    let key_names = |keys: Vec<KeyCode>| {
+        keys.into_iter()
+            .map(interaction::key_name)
+            .collect::<Vec<_>>()
+    };
+    let mut help = vec![(key_names(interaction::advance_keys()).join(", "), "Next")];
+
+    help.extend(
+        HELP.iter()
+            .map(|&(keys, action)| (keys.to_string(), action)),
+    );
+
+    let quit_keys = key_names(interaction::quit_keys());
+
+    if !quit_keys.is_empty() {
+        help.push((quit_keys.join(", "), "Quit"));
+    }
+
+    let key_width = help
+        .iter()
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut lines: Vec<String> = help
+        .iter()
+        .map(|(keys, action)| format!("{keys:<key_width$}  {action}"))
+        .collect();
+
+    lines.push(String::new());
+    lines.push("Press any key to close this help.".to_string());
+
+    let inner_width = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0)
+        + 2;
+    let (columns, rows) = output::terminal_size().unwrap_or((80, 24));
+    let column = columns.saturating_sub(inner_width as u16 + 2) / 2;
+    let row = rows.saturating_sub(lines.len() as u16 + 2) / 2;
+    let mut output = output::output();
+
+    queue!(
+        output,
+        MoveTo(column, row),
+        Print(format!("┌{}┐", "─".repeat(inner_width)))
+    )?;
+
+    for (offset, line) in lines.iter().enumerate() {
+        queue!(
+            output,
+            MoveTo(column, row + 1 + offset as u16),
+            Print(format!("│ {line:<width$} │", width = inner_width - 2)),
+        )?;
+    }
+
+    queue!(
+        output,
+        MoveTo(column, row + 1 + lines.len() as u16),
+        Print(format!("└{}┘", "─".repeat(inner_width))),
+    )?;
+    output.flush()?;
+
+    wait_for_key(|_| true)
+}
+
+/// Blanks the screen, then waits for any key to be pressed.
+fn show_black_screen() -> crossterm::Result<()> {
+    execute!(
+        output::output(),
+        SetBackgroundColor(Color::Black),
+        Clear(ClearType::All),
+        ResetColor,
+        Hide,
+    )?;
+    wait_for_key(|_| true)?;
+    execute!(output::output(), Show)
+}
+
+/// Shows a message saying the terminal is too small until it's at least `columns` wide and `rows` tall,
+/// returning `false` if a quit key was pressed before then.
+///
+/// There's no telling whether the terminal can be made any bigger, so Ctrl+C and the quit keys still work meanwhile,
+/// and `q` and Escape do too while the presentation is looping.
+fn wait_for_size(columns: u16, rows: u16) -> crossterm::Result<bool> {
+    if layout::fits(columns, rows) {
+        return Ok(true);
+    }
+
+    let _raw_mode = RawModeGuard::enable()?;
+
+    let mut quit = false;
+
+    'resizing: while !layout::fits(columns, rows) {
+        let mut message = String::new();
+
+        layout::write_too_small(&mut message, columns, rows)
+            .expect("the message should be written to a string");
+        execute!(
+            output::output(),
+            Clear(ClearType::All),
+            MoveTo(0, 0),
+            Print(message)
+        )?;
+
+        loop {
+            match event::read()? {
+                Event::Resize(_, _) => break,
+                Event::Key(key) => {
+                    if let Some(error) = interaction::press_ctrl_c(key) {
+                        return Err(error);
+                    }
+
+                    if interaction::is_quit_key(key.code)
+                        || runner::advance_after().is_some()
+                            && matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+                    {
+                        interaction::set_quit_requested(true);
+                        quit = true;
+                        break 'resizing;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    execute!(output::output(), Clear(ClearType::All), MoveTo(0, 0))?;
+
+    Ok(!quit)
+}
+
+/// Waits for `duration` in raw mode, returning `true` if `q` or Escape was pressed before it passed,
+/// or an error if Ctrl+C was, unless it's ignored (see [`set_ctrl_c`](crate::set_ctrl_c)).
+fn wait_for_quit(duration: Duration) -> crossterm::Result<bool> {
+    let _raw_mode = RawModeGuard::enable()?;
+
+    let started = Instant::now();
+    let mut quit = false;
+
+    while let Some(remaining) = duration.checked_sub(started.elapsed()) {
+        if !event::poll(remaining)? {
+            break;
+        }
+
+        if let Event::Key(key) = event::read()? {
+            if let Some(error) = interaction::press_ctrl_c(key) {
+                return Err(error);
+            }
+
+            if matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+                quit = true;
+                break;
+            }
+        }
+    }
+
+    Ok(quit)
+}
+
+/// Waits in raw mode until a key for which `accept` returns `true` is pressed.
+///
+/// Ctrl+C returns an error instead, unless it's ignored, see [`set_ctrl_c`](crate::set_ctrl_c).
+fn wait_for_key(accept: impl Fn(KeyCode) -> bool) -> crossterm::Result<()> {
+    let _raw_mode = RawModeGuard::enable()?;
+
+    loop {
+        if let Event::Key(key) = event::read()? {
+            if let Some(error) = interaction::press_ctrl_c(key) {
+                return Err(error);
+            }
+
+            if accept(key.code) {
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A slide that failed to render, as reported by [`Presentation::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlideFailure {
+    /// The index of the slide in the presentation, starting at `0`.
+    pub index: usize,
+    /// The error or panic message of the slide.
+    pub message: String,
+}
+
+/// A screen shown before the first slide of a [`Presentation`], until there's an interaction.
+///
+/// This is useful for starting a recording before the presentation actually begins.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Splash {
+    /// The text shown on the splash screen.
+    pub text: String,
+    /// The style of the text shown on the splash screen.
+    pub style: ContentStyle,
+}
+
+impl Splash {
+    /// Clears the screen, prints the splash text and waits for an interaction.
+    pub fn show(&self) -> crossterm::Result<()> {
+        execute!(
+            output::output(),
+            Clear(ClearType::All),
+            PrintStyledContent(self.style.apply(&self.text)),
+            WaitForInteraction,
+        )
+    }
+}
+
+impl Default for Splash {
+    fn default() -> Self {
+        Self {
+            text: "Press space to begin".to_string(),
+            style: ContentStyle::new().italic(),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Where a slide is in the presentation, shown in a corner of the terminal.
+
+use crate::{layout, Corner};
+use crossterm::cursor::{MoveTo, RestorePosition, SavePosition};
+use crossterm::style::{Print, Stylize};
+use crossterm::Command;
+use std::fmt;
+
+/// A command that prints how far through the presentation a slide is, like `3 / 12`, dimmed in a corner of the terminal,
+/// leaving the cursor where it was.
+///
+/// If the terminal is too narrow for it, nothing is printed, so small terminals don't get a broken footer.
+/// To keep the progress in a corner of every slide of a presentation,
+/// use [`Presentation::slide_progress`](crate::Presentation::slide_progress) instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, Corner, SlideProgress, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// slide!(
+///     SlideProgress {
+///         current: 3,
+///         total: 12,
+///         corner: Corner::BottomRight,
+///     },
+///     TypewriterPrint("This is the third slide.", Duration::from_millis(25)),
+/// )
+/// .expect("the progress should be printed in the bottom right corner");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`SlideProgress`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SlideProgress {
+    /// The number of the slide, starting at `1`.
+    pub current: usize,
+    /// The number of slides in the presentation.
+    pub total: usize,
+    /// The corner the progress is printed in.
+    pub corner: Corner,
+}
+
+impl Command for SlideProgress {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let progress = format!("{} / {}", self.current, self.total);
+        let width = u16::try_from(progress.len()).unwrap_or(u16::MAX);
+
+        if !layout::fits(width, 1) {
+            return Ok(());
+        }
+
+        let (column, row) = self.corner.position(width);
+
+        SavePosition.write_ansi(f)?;
+        MoveTo(column, row).write_ansi(f)?;
+        Print(progress.dim()).write_ansi(f)?;
+        RestorePosition.write_ansi(f)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute SlideProgress command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,160 @@
+//! QR codes drawn with half-block characters.
+
+use crate::{layout, output, text};
+use crossterm::cursor::MoveToColumn;
+use crossterm::style::{Color, PrintStyledContent, Stylize};
+use crossterm::terminal;
+use crossterm::Command;
+use qrcode::types::QrError;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// The number of light modules around the code, which scanners need to find it.
+const QUIET_ZONE: usize = 2;
+
+/// A command that draws a QR code, centered horizontally.
+///
+/// Each character holds two modules, one above the other, using half-block characters,
+/// so the code is as small as it can be while staying scannable.
+/// The modules are drawn in black and white regardless of the terminal's colors.
+/// If the terminal has shrunk below [`size`](QrCode::size) since the code was created,
+/// a message saying so is printed instead of a code that can't be scanned.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, QrCode};
+///
+/// slide!(QrCode::new("https://github.com/valentinegb/clp").expect("the QR code should fit"))
+///     .expect("the QR code should be drawn");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`QrCode`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrCode {
+    modules: Vec<bool>,
+    width: usize,
+}
+
+impl QrCode {
+    /// Encodes `data` into a QR code.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` can't be encoded,
+    /// or if the code would be too large for the terminal.
+    pub fn new(data: impl AsRef<[u8]>) -> Result<Self, QrCodeError> {
+        let code = qrcode::QrCode::new(data).map_err(QrCodeError::Encoding)?;
+        let width = code.width() + QUIET_ZONE * 2;
+        let mut modules = vec![false; width * width];
+
+        for (index, color) in code.to_colors().into_iter().enumerate() {
+            let (x, y) = (index % code.width(), index / code.width());
+
+            modules[(y + QUIET_ZONE) * width + x + QUIET_ZONE] = color == qrcode::Color::Dark;
+        }
+
+        let qr_code = Self { modules, width };
+        let (columns, rows) = qr_code.size();
+
+        if let Ok((terminal_columns, terminal_rows)) = terminal::size() {
+            if columns > terminal_columns || rows > terminal_rows {
+                return Err(QrCodeError::TooLarge { columns, rows });
+            }
+        }
+
+        Ok(qr_code)
+    }
+
+    /// Returns the number of columns and rows the code takes up in the terminal.
+    pub fn size(&self) -> (u16, u16) {
+        let columns = u16::try_from(self.width).unwrap_or(u16::MAX);
+        let rows = u16::try_from(self.width.div_ceil(2)).unwrap_or(u16::MAX);
+
+        (columns, rows)
+    }
+
+    fn is_dark(&self, x: usize, y: usize) -> bool {
+        y < self.width && self.modules[y * self.width + x]
+    }
+}
+
+impl Command for QrCode {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let (columns, rows) = self.size();
+
+        if layout::write_too_small(f, columns, rows)? {
+            return Ok(());
+        }
+
+        let terminal_columns = output::terminal_size().map_or(columns, |(columns, _)| columns);
+        let padding = terminal_columns.saturating_sub(columns) / 2;
+        let color = |dark| if dark { Color::Black } else { Color::White };
+
+        for y in (0..self.width).step_by(2) {
+            MoveToColumn(padding).write_ansi(f)?;
+
+            for x in 0..self.width {
+                PrintStyledContent(
+                    '▀'
+                        .with(color(self.is_dark(x, y)))
+                        .on(color(self.is_dark(x, y + 1))),
+                )
+                .write_ansi(f)?;
+            }
+
+            text::write_newline(f)?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute QrCode command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// An error that occurred while creating a [`QrCode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QrCodeError {
+    /// The data couldn't be encoded, usually because there's too much of it.
+    Encoding(QrError),
+    /// The code would take up more columns or rows than the terminal has.
+    TooLarge {
+        /// The number of columns the code would take up.
+        columns: u16,
+        /// The number of rows the code would take up.
+        rows: u16,
+    },
+}
+
+impl Display for QrCodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Encoding(error) => write!(f, "data couldn't be encoded as a QR code: {error}"),
+            Self::TooLarge { columns, rows } => write!(
+                f,
+                "QR code needs {columns}x{rows} characters, which doesn't fit in the terminal"
+            ),
+        }
+    }
+}
+
+impl Error for QrCodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Encoding(error) => Some(error),
+            Self::TooLarge { .. } => None,
+        }
+    }
+}
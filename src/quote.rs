@@ -0,0 +1,78 @@
+//! Quotes with an attribution, laid out like they would be on a quote slide.
+
+use crate::layout::{self, content_area, with_content_width};
+use crate::typing::typewrite;
+use crate::{text, Typing};
+use crossterm::cursor::MoveToColumn;
+use crossterm::style::{Attribute, ContentStyle};
+use crossterm::Command;
+use std::fmt::{self, Display};
+
+/// A command that types a quote in italics, centered line by line,
+/// followed by `— author` aligned to the right beneath it.
+///
+/// The quote is word-wrapped to the content width of the running [`Presentation`](crate::Presentation)
+/// (see [`Presentation::content_width`](crate::Presentation::content_width)), or to the terminal's width otherwise.
+/// Both the quote and the attribution are typed with the slide's typing options, see [`Typing::inherit`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, Quote};
+///
+/// slide!(Quote(
+///     "Simplicity is prerequisite for reliability.",
+///     "Edsger W. Dijkstra",
+/// ))
+/// .expect("the quote should be typed with its attribution");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`Quote`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quote<T: Display, A: Display>(pub T, pub A);
+
+impl<T: Display, A: Display> Command for Quote<T, A> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let (padding, width) = content_area();
+        let quote = layout::wrap(
+            &text::normalize_newlines(&self.0.to_string()),
+            width as usize,
+            0,
+        );
+        let attribution = format!("— {}", self.1);
+        let italic = ContentStyle {
+            attributes: Attribute::Italic.into(),
+            ..ContentStyle::new()
+        };
+        // The lines are already laid out, so they shouldn't be wrapped and centered again while typing.
+        with_content_width(None, || {
+            for line in quote.split('\n') {
+                let line = line.trim_end();
+                let line_width = u16::try_from(layout::width(line)).unwrap_or(width);
+
+                MoveToColumn(padding + width.saturating_sub(line_width) / 2).write_ansi(f)?;
+                typewrite(f, line, Some(italic), Typing::inherit())?;
+                text::write_newline(f)?;
+            }
+
+            let attribution_width = u16::try_from(layout::width(&attribution)).unwrap_or(width);
+
+            MoveToColumn(padding + width.saturating_sub(attribution_width)).write_ansi(f)?;
+            typewrite(f, &attribution, None, Typing::inherit())
+        })
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute Quote command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
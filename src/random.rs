@@ -0,0 +1,53 @@
+//! A tiny random number generator for effects that need randomness.
+
+use std::cell::Cell;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+thread_local! {
+    static STATE: Cell<u64> = Cell::new(nonzero(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64),
+    ));
+}
+
+/// Seeds the random number generator used by effects on the current thread, such as [`DecodePrint`](crate::DecodePrint),
+/// and by typing jitter (see [`Typing::with_jitter`](crate::Typing::with_jitter)).
+///
+/// Without a seed, the generator is seeded from the system clock.
+/// Seeding it makes effects play out the same way every time, which is useful for tests.
+pub fn seed_random(seed: u64) {
+    STATE.with(|state| state.set(nonzero(seed)));
+}
+
+/// Returns the next random number, using xorshift64*.
+pub(crate) fn next_u64() -> u64 {
+    STATE.with(|state| {
+        let mut x = state.get();
+
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        state.set(x);
+
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    })
+}
+
+/// Returns a random number in `0..bound`, or `0` if `bound` is `0`.
+pub(crate) fn below(bound: usize) -> usize {
+    if bound == 0 {
+        return 0;
+    }
+
+    (next_u64() % bound as u64) as usize
+}
+
+/// Maps a seed of `0`, which xorshift can't use, to another constant.
+fn nonzero(seed: u64) -> u64 {
+    if seed == 0 {
+        0x9E37_79B9_7F4A_7C15
+    } else {
+        seed
+    }
+}
@@ -0,0 +1,149 @@
+//! Recording slides to asciinema cast files.
+
+use crate::output;
+use std::fmt::Write as _;
+use std::io::{self, stdout, Write};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// A stream that records everything written to it as an [asciinema v2 cast file](https://docs.asciinema.org/manual/asciicast/v2/),
+/// which can be played back or embedded on a website without screen recording.
+///
+/// Everything written between two flushes is recorded as one event, timestamped with when it was flushed,
+/// so the delays between typed characters are kept and playback looks just like the live presentation.
+/// The header is written as soon as the recorder is created, with the size of the terminal at that time.
+/// Everything is also written to standard output, so the presentation can still be seen while it's recorded,
+/// unless [`without_terminal`](Recorder::without_terminal) is used.
+///
+/// To record slides, set the recorder as the output with [`set_output`](crate::set_output).
+/// Anything not flushed yet is recorded when the recorder is dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{set_output, slide, Recorder, TypewriterPrint};
+/// use std::fs::File;
+/// use std::time::Duration;
+///
+/// let file = File::create("talk.cast").expect("the cast file should be created");
+/// let recorder = Recorder::new(file).expect("the header should be written");
+///
+/// set_output(Some(Box::new(recorder))).expect("the slides should be recorded from now on");
+/// slide!(TypewriterPrint("Hello, world!", Duration::from_millis(25)))
+///     .expect("\"Hello, world!\" should be typed and recorded");
+/// set_output(None).expect("the recording should be finished");
+/// ```
+#[derive(Debug)]
+pub struct Recorder<W: Write> {
+    cast: W,
+    terminal: bool,
+    started: Instant,
+    pending: Vec<u8>,
+}
+
+impl<W: Write> Recorder<W> {
+    /// Creates a recorder that writes the cast file to `cast`, writing its header right away.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the header can't be written.
+    pub fn new(mut cast: W) -> io::Result<Self> {
+        let (width, height) = output::terminal_size().unwrap_or((80, 24));
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+
+        writeln!(
+            cast,
+            r#"{{"version": 2, "width": {width}, "height": {height}, "timestamp": {timestamp}}}"#
+        )?;
+
+        Ok(Self {
+            cast,
+            terminal: true,
+            started: Instant::now(),
+            pending: Vec::new(),
+        })
+    }
+
+    /// Stops the recorder from also writing to standard output, so the presentation is only recorded.
+    ///
+    /// Interactions are still read from the terminal, so they have to be made without seeing the slides.
+    pub fn without_terminal(mut self) -> Self {
+        self.terminal = false;
+        self
+    }
+
+    /// Records everything written since the last event as a new one, keeping back an incomplete character at the end.
+    fn record(&mut self) -> io::Result<()> {
+        let complete = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            // An error without a length is an incomplete character at the end, which the next write completes.
+            Err(error) if error.error_len().is_none() => error.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+
+        if complete == 0 {
+            return Ok(());
+        }
+
+        let written: Vec<u8> = self.pending.drain(..complete).collect();
+        let event = json_string(&String::from_utf8_lossy(&written));
+
+        writeln!(
+            self.cast,
+            "[{:.6}, \"o\", {event}]",
+            self.started.elapsed().as_secs_f64()
+        )?;
+        self.cast.flush()
+    }
+}
+
+impl<W: Write> Write for Recorder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.terminal {
+            stdout().write_all(buf)?;
+        }
+
+        self.pending.extend_from_slice(buf);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.terminal {
+            stdout().flush()?;
+        }
+
+        self.record()
+    }
+}
+
+impl<W: Write> Drop for Recorder<W> {
+    fn drop(&mut self) {
+        let _ = self.record();
+    }
+}
+
+/// Returns `text` as a JSON string, quoted and escaped.
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+
+    json.push('"');
+
+    for char in text.chars() {
+        match char {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            char if char.is_control() => {
+                let _ = write!(json, "\\u{:04x}", char as u32);
+            }
+            char => json.push(char),
+        }
+    }
+
+    json.push('"');
+    json
+}
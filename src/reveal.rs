@@ -0,0 +1,99 @@
+//! Lists that are revealed one item at a time.
+
+use crate::typing::typewrite;
+use crate::{layout, text, Typing, WaitForInteraction};
+use crossterm::cursor::MoveToPreviousLine;
+use crossterm::style::{ContentStyle, Stylize};
+use crossterm::Command;
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+/// A command that types a list one item at a time, waiting for an interaction before each item after the first.
+///
+/// Each item is typed on its own line.
+/// If [`dim_previous`](Reveal::dim_previous) is set,
+/// the previous item is redrawn dimmed whenever the next one appears, keeping focus on the current point.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, Reveal};
+/// use std::time::Duration;
+///
+/// let mut reveal = Reveal::new(
+///     vec!["- First point", "- Second point", "- Third point"],
+///     Duration::from_millis(25),
+/// );
+///
+/// reveal.dim_previous = true;
+///
+/// slide!(reveal).expect("each point should be revealed after an interaction");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`Reveal`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reveal<T: Display> {
+    /// The items of the list.
+    pub items: Vec<T>,
+    /// How each item is typed.
+    pub typing: Typing,
+    /// Whether the previous item is dimmed when the next one is revealed.
+    pub dim_previous: bool,
+}
+
+impl<T: Display> Reveal<T> {
+    /// Creates a list that's typed with the given delay between characters, without dimming.
+    pub fn new(items: Vec<T>, typing: impl Into<Typing>) -> Self {
+        Self {
+            items,
+            typing: typing.into(),
+            dim_previous: false,
+        }
+    }
+}
+
+impl<T: Display> Command for Reveal<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let mut previous: Option<String> = None;
+
+        for item in &self.items {
+            let item = item.to_string();
+
+            if let Some(previous) = &previous {
+                WaitForInteraction.write_ansi(f)?;
+
+                if self.dim_previous {
+                    MoveToPreviousLine(layout::rows(previous)).write_ansi(f)?;
+                    typewrite(
+                        f,
+                        previous,
+                        Some(ContentStyle::new().dim()),
+                        Typing::new(Duration::ZERO),
+                    )?;
+                    text::write_newline(f)?;
+                }
+            }
+
+            typewrite(f, &item, None, self.typing)?;
+            text::write_newline(f)?;
+
+            previous = Some(item);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute Reveal command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,313 @@
+//! State shared between a running [`Presentation`](crate::Presentation) and the commands of its slides.
+//!
+//! Commands can't return anything other than formatting errors, so when a key pressed during a wait
+//! needs the presentation to do something (like showing the help overlay),
+//! the wait records an [`Interrupt`] here and the rest of the slide is skipped.
+//! The presentation then handles the interrupt and, if needed,
+//! re-renders the slide by fast-forwarding through the waits it had already passed.
+
+use crate::{Corner, SlideProgress};
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static STATE: Cell<State> = const {
+        Cell::new(State {
+            running: false,
+            manual: false,
+            advance_after: None,
+            stopped: false,
+            catching_up: false,
+            interrupt: None,
+            waits_passed: 0,
+            fast_forward: 0,
+            started: None,
+            clock: None,
+            progress: None,
+            max_slide_duration: None,
+            slide_started: None,
+        })
+    };
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    running: bool,
+    manual: bool,
+    advance_after: Option<Duration>,
+    stopped: bool,
+    catching_up: bool,
+    interrupt: Option<Interrupt>,
+    waits_passed: usize,
+    fast_forward: usize,
+    started: Option<Instant>,
+    clock: Option<Corner>,
+    progress: Option<SlideProgress>,
+    max_slide_duration: Option<Duration>,
+    slide_started: Option<Instant>,
+}
+
+/// Something a key pressed during a wait asks the running presentation to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Interrupt {
+    /// Shows the help overlay, then restores the slide.
+    Help,
+    /// Blanks the screen until any key is pressed, then restores the slide without passing its wait.
+    BlackScreen,
+    /// Shows the overview of the slides, then jumps to the chosen slide or restores the current one.
+    Overview,
+    /// Restores the slide as it was before its last wait was passed.
+    StepBack,
+    /// Restores the slide laid out for the new size of the terminal.
+    Resize,
+    /// Stops the presentation.
+    Quit,
+}
+
+/// Marks a presentation as running until it's dropped.
+pub(crate) struct Running {
+    previous: State,
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        let previous = self.previous;
+
+        update(|state| {
+            state.running = previous.running;
+            state.manual = previous.manual;
+            state.advance_after = previous.advance_after;
+            state.started = previous.started;
+            state.clock = previous.clock;
+            state.progress = previous.progress;
+            state.max_slide_duration = previous.max_slide_duration;
+            state.slide_started = previous.slide_started;
+        });
+    }
+}
+
+/// Marks a presentation as running until the returned guard is dropped,
+/// showing the elapsed time in `clock` while waiting for interactions if it's set,
+/// and advancing slides shown for longer than `max_slide_duration` if it's set.
+pub(crate) fn start(clock: Option<Corner>, max_slide_duration: Option<Duration>) -> Running {
+    let previous = STATE.with(Cell::get);
+
+    update(|state| {
+        state.running = true;
+        state.manual = false;
+        state.advance_after = None;
+        state.started = Some(Instant::now());
+        state.clock = clock;
+        state.progress = None;
+        state.max_slide_duration = max_slide_duration;
+        state.slide_started = None;
+    });
+
+    Running { previous }
+}
+
+/// Marks a presentation as being stepped through manually until the returned guard is dropped,
+/// which stops its slides at their first wait that isn't fast-forwarded instead of waiting.
+pub(crate) fn start_manual() -> Running {
+    let previous = STATE.with(Cell::get);
+
+    update(|state| {
+        state.running = false;
+        state.manual = true;
+        state.advance_after = None;
+        state.started = None;
+        state.clock = None;
+        state.progress = None;
+        state.max_slide_duration = None;
+        state.slide_started = None;
+    });
+
+    Running { previous }
+}
+
+/// Marks a presentation as looping unattended until the returned guard is dropped,
+/// which passes every wait by itself after `advance_after`, and stops it if a quit key is pressed instead.
+pub(crate) fn start_loop(clock: Option<Corner>, advance_after: Duration) -> Running {
+    let previous = STATE.with(Cell::get);
+
+    update(|state| {
+        state.running = false;
+        state.manual = false;
+        state.advance_after = Some(advance_after);
+        state.started = Some(Instant::now());
+        state.clock = clock;
+        state.progress = None;
+        state.max_slide_duration = None;
+        state.slide_started = None;
+    });
+
+    Running { previous }
+}
+
+/// Returns how long waits take to pass by themselves while a presentation is looping, if it is.
+pub(crate) fn advance_after() -> Option<Duration> {
+    STATE.with(Cell::get).advance_after
+}
+
+/// Returns `true` while a presentation is being stepped through manually.
+pub(crate) fn is_manual() -> bool {
+    STATE.with(Cell::get).manual
+}
+
+/// Stops the current slide of a presentation being stepped through manually at a wait,
+/// so the rest of it is rendered without delays or waits only to find out if there's any more to it.
+pub(crate) fn stop() {
+    update(|state| state.stopped = true);
+}
+
+/// Returns `true` if the current slide has been stopped at a wait.
+pub(crate) fn is_stopped() -> bool {
+    STATE.with(Cell::get).stopped
+}
+
+/// Returns `true` while a presentation is running.
+pub(crate) fn is_running() -> bool {
+    STATE.with(Cell::get).running
+}
+
+/// Returns the time elapsed since the running presentation started, or zero if none is running.
+pub(crate) fn elapsed() -> Duration {
+    STATE
+        .with(Cell::get)
+        .started
+        .map_or(Duration::ZERO, |started| started.elapsed())
+}
+
+/// Returns the corner the elapsed time is shown in while waiting for interactions, if it's shown.
+pub(crate) fn clock() -> Option<Corner> {
+    STATE.with(Cell::get).clock
+}
+
+/// Sets the progress shown while waiting for interactions, if it's shown.
+pub(crate) fn set_progress(progress: Option<SlideProgress>) {
+    update(|state| state.progress = progress);
+}
+
+/// Returns the progress shown while waiting for interactions, if it's shown.
+pub(crate) fn progress() -> Option<SlideProgress> {
+    STATE.with(Cell::get).progress
+}
+
+/// Starts timing how long the current slide has been shown for.
+pub(crate) fn start_slide_timer() {
+    update(|state| state.slide_started = Some(Instant::now()));
+}
+
+/// Returns when the current slide of the running presentation was first shown, if there is one.
+pub(crate) fn slide_started() -> Option<Instant> {
+    STATE.with(Cell::get).slide_started
+}
+
+/// Returns when the current slide will have been shown for the maximum slide duration, if there is one.
+pub(crate) fn slide_deadline() -> Option<Instant> {
+    let state = STATE.with(Cell::get);
+
+    Some(state.slide_started? + state.max_slide_duration?)
+}
+
+/// Prepares for rendering a slide, instantly passing its first `fast_forward` waits.
+pub(crate) fn begin_slide(fast_forward: usize) {
+    update(|state| {
+        state.interrupt = None;
+        state.stopped = false;
+        state.catching_up = false;
+        state.waits_passed = 0;
+        state.fast_forward = fast_forward;
+    });
+}
+
+/// Skips the delays of the current slide until it actually waits,
+/// so it's restored instantly up to that point instead of typing the part after its fast-forwarded waits again.
+pub(crate) fn catch_up() {
+    update(|state| state.catching_up = true);
+}
+
+/// Stops skipping delays once a slide actually waits, see [`catch_up`].
+pub(crate) fn caught_up() {
+    update(|state| state.catching_up = false);
+}
+
+/// Skips the delays of the current slide until its next wait, also passing that wait if `pass` is `true`.
+pub(crate) fn skip_to_wait(pass: bool) {
+    update(|state| {
+        if pass {
+            state.fast_forward += 1;
+        } else {
+            state.catching_up = true;
+        }
+    });
+}
+
+/// Interrupts the current slide, so the rest of it is skipped.
+pub(crate) fn interrupt(interrupt: Interrupt) {
+    update(|state| state.interrupt = Some(interrupt));
+}
+
+/// Returns `true` if the current slide has been interrupted.
+pub(crate) fn is_interrupted() -> bool {
+    STATE.with(Cell::get).interrupt.is_some()
+}
+
+/// Takes the interrupt of the current slide, if it was interrupted.
+pub(crate) fn take_interrupt() -> Option<Interrupt> {
+    STATE.with(|state| {
+        let mut current = state.get();
+        let interrupt = current.interrupt.take();
+
+        state.set(current);
+
+        interrupt
+    })
+}
+
+/// Returns the number of waits the current slide has passed.
+pub(crate) fn waits_passed() -> usize {
+    STATE.with(Cell::get).waits_passed
+}
+
+/// Returns `true` if delays should be skipped,
+/// because the slide was interrupted, stopped, is being fast-forwarded or is catching up.
+pub(crate) fn is_skipping_delays() -> bool {
+    let state = STATE.with(Cell::get);
+
+    state.interrupt.is_some() || state.stopped || state.catching_up || state.fast_forward > 0
+}
+
+/// Returns `true` if a wait should be passed without waiting, counting it as passed if so.
+pub(crate) fn skip_wait() -> bool {
+    let state = STATE.with(Cell::get);
+
+    if state.interrupt.is_some() || state.stopped {
+        return true;
+    }
+
+    if state.fast_forward > 0 {
+        update(|state| {
+            state.fast_forward -= 1;
+            state.waits_passed += 1;
+        });
+
+        return true;
+    }
+
+    false
+}
+
+/// Counts a wait of the current slide as passed.
+pub(crate) fn pass_wait() {
+    update(|state| state.waits_passed += 1);
+}
+
+fn update(f: impl FnOnce(&mut State)) {
+    STATE.with(|state| {
+        let mut current = state.get();
+
+        f(&mut current);
+        state.set(current);
+    });
+}
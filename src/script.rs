@@ -0,0 +1,79 @@
+//! Timelines of actions defined as data and played back in order.
+
+use crate::{output, TypewriterPrint, WaitFor, WaitForInteraction, WaitForKey};
+use crossterm::cursor::MoveTo;
+use crossterm::event::KeyCode;
+use crossterm::execute;
+use crossterm::style::Print;
+use crossterm::terminal::{Clear, ClearType};
+use std::time::Duration;
+
+/// Something that an [`EventScript`] does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Types text with the given delay between characters, like [`TypewriterPrint`].
+    Type(String, Duration),
+    /// Prints text all at once.
+    Print(String),
+    /// Clears the terminal and moves the cursor to its top left corner.
+    Clear,
+    /// Waits for an interaction, like [`WaitForInteraction`].
+    WaitForInteraction,
+    /// Waits for a specific key to be pressed, like [`WaitForKey`].
+    WaitForKey(KeyCode),
+}
+
+/// A timeline of actions, each one performed after its delay since the previous one.
+///
+/// Unlike nesting commands in [`slide`](crate::slide), a script is plain data,
+/// so it can be built up programmatically, which suits demo reels that play by themselves.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{Action, EventScript};
+/// use std::time::Duration;
+///
+/// EventScript(vec![
+///     (Duration::ZERO, Action::Type("Loading...".to_string(), Duration::from_millis(25))),
+///     (Duration::from_secs(2), Action::Clear),
+///     (Duration::ZERO, Action::Type("Done!".to_string(), Duration::from_millis(25))),
+///     (Duration::ZERO, Action::WaitForInteraction),
+/// ])
+/// .play()
+/// .expect("the script should play");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EventScript(pub Vec<(Duration, Action)>);
+
+impl EventScript {
+    /// Creates a script without any actions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an action to the end of the script, performed `delay` after the previous one.
+    pub fn then(mut self, delay: Duration, action: Action) -> Self {
+        self.0.push((delay, action));
+        self
+    }
+
+    /// Performs every action of the script, in order.
+    pub fn play(&self) -> crossterm::Result<()> {
+        for (delay, action) in &self.0 {
+            if !delay.is_zero() {
+                execute!(output(), WaitFor::uninterruptible(*delay))?;
+            }
+
+            match action {
+                Action::Type(text, delay) => execute!(output(), TypewriterPrint(text, *delay))?,
+                Action::Print(text) => execute!(output(), Print(text))?,
+                Action::Clear => execute!(output(), Clear(ClearType::All), MoveTo(0, 0))?,
+                Action::WaitForInteraction => execute!(output(), WaitForInteraction)?,
+                Action::WaitForKey(code) => execute!(output(), WaitForKey(*code))?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,252 @@
+//! Raw mode shared by all the commands of a slide.
+//!
+//! Without a session, every pause enables raw mode so keypresses aren't echoed and disables it again afterward.
+//! Typewriter commands start a session of their own so raw mode isn't toggled around each of their delays,
+//! and so it's restored even if they panic, but that still adds a gap between adjacent commands.
+//! The [`slide`](crate::slide) macro keeps raw mode enabled for the whole slide instead,
+//! and [`Output`](crate::Output) turns lone `\n`s into `\r\n`s meanwhile,
+//! so commands that expect line breaks to return to the first column still work.
+//! The cursor is hidden for as long as a session lasts too, see [`set_cursor_hiding`].
+
+use crate::output::{self, output};
+use crossterm::cursor::{Hide, Show};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled};
+use crossterm::{execute, queue};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+thread_local! {
+    static ACTIVE: Cell<bool> = const { Cell::new(false) };
+}
+
+static RAW_MODE: RwLock<RawMode> = RwLock::new(RawMode::Auto);
+static CURSOR_HIDING: AtomicBool = AtomicBool::new(true);
+
+/// Whether raw mode is enabled while typing, see [`set_raw_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RawMode {
+    /// Raw mode is enabled if standard output is a terminal.
+    #[default]
+    Auto,
+    /// Raw mode is always enabled.
+    Always,
+    /// Raw mode is never enabled.
+    Never,
+}
+
+/// Sets whether raw mode is enabled while typing, which it is by default if standard output is a terminal.
+///
+/// Slides keep raw mode enabled while they're shown, and so does typing between characters,
+/// so keypresses aren't echoed in the middle of the slide.
+/// When output is piped into another program or a file rather than shown in a terminal, that isn't needed,
+/// so with [`RawMode::Never`], text is typed by just writing characters and sleeping between them.
+/// Waiting for interactions, like [`WaitForInteraction`](crate::WaitForInteraction),
+/// still enables raw mode, since it's needed to read keys.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{set_raw_mode, slide, RawMode, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// set_raw_mode(RawMode::Never);
+///
+/// slide!(
+///     wait: none;
+///     TypewriterPrint("This can be piped into pv.", Duration::from_millis(25)),
+/// )
+/// .expect("the text should be typed without raw mode");
+/// ```
+pub fn set_raw_mode(mode: RawMode) {
+    *RAW_MODE
+        .write()
+        .expect("raw mode lock should not be poisoned") = mode;
+}
+
+/// Returns whether raw mode is enabled while typing.
+pub fn raw_mode() -> RawMode {
+    *RAW_MODE
+        .read()
+        .expect("raw mode lock should not be poisoned")
+}
+
+/// Sets whether the cursor is hidden while slides are shown, which it is by default.
+///
+/// The cursor is hidden for as long as a slide keeps raw mode enabled, so it doesn't blink at the end of typed text,
+/// and it's shown again when the slide is over, even if it panics.
+/// Like raw mode, that's only while standard output is a terminal, unless changed with [`set_raw_mode`].
+/// This can be changed between slides to keep the cursor visible for some of them, like those showing a live demo.
+/// If the process exits in the middle of a slide, [`reset_terminal`](crate::reset_terminal) shows the cursor again.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{set_cursor_hiding, slide, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// set_cursor_hiding(false);
+///
+/// slide!(TypewriterPrint("$ cargo run", Duration::from_millis(50)))
+///     .expect("the command should be typed with the cursor visible");
+/// ```
+pub fn set_cursor_hiding(enabled: bool) {
+    CURSOR_HIDING.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether the cursor is hidden while slides are shown.
+pub fn cursor_hiding() -> bool {
+    CURSOR_HIDING.load(Ordering::Relaxed)
+}
+
+/// Returns `true` if raw mode should be enabled while typing, as set with [`set_raw_mode`].
+pub(crate) fn uses_raw_mode() -> bool {
+    match raw_mode() {
+        RawMode::Auto => output::is_terminal(),
+        RawMode::Always => true,
+        RawMode::Never => false,
+    }
+}
+
+/// Keeps raw mode enabled and the cursor hidden until it's dropped, see [`start`].
+#[derive(Debug)]
+pub struct Session {
+    owned: bool,
+    was_raw_mode_enabled: bool,
+    hid_cursor: bool,
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        if !self.owned {
+            return;
+        }
+
+        ACTIVE.with(|active| active.set(false));
+
+        if self.hid_cursor {
+            let _ = execute!(output(), Show);
+        }
+
+        if !self.was_raw_mode_enabled {
+            let _ = disable_raw_mode();
+        }
+    }
+}
+
+/// Keeps raw mode enabled until the returned session is dropped, unless a session is already active,
+/// hiding the cursor meanwhile if [`cursor_hiding`] is enabled.
+///
+/// Nothing is done while rendering headlessly, or if raw mode isn't used while typing (see [`set_raw_mode`]).
+#[doc(hidden)]
+pub fn start() -> Session {
+    if is_active() || output::is_headless() || !uses_raw_mode() {
+        return Session {
+            owned: false,
+            was_raw_mode_enabled: true,
+            hid_cursor: false,
+        };
+    }
+
+    let was_raw_mode_enabled = is_raw_mode_enabled().unwrap_or(true);
+
+    if !was_raw_mode_enabled && enable_raw_mode().is_err() {
+        return Session {
+            owned: false,
+            was_raw_mode_enabled: true,
+            hid_cursor: false,
+        };
+    }
+
+    ACTIVE.with(|active| active.set(true));
+
+    let hid_cursor = cursor_hiding();
+
+    if hid_cursor {
+        if let Err(error) = queue!(output(), Hide) {
+            output::fail(error);
+        }
+    }
+
+    Session {
+        owned: true,
+        was_raw_mode_enabled,
+        hid_cursor,
+    }
+}
+
+/// Keeps raw mode enabled for reading keys until it's dropped, then restores it to how it was before,
+/// even if reading the keys fails or panics.
+///
+/// Unlike a [`Session`], raw mode is enabled whether or not it's used while typing (see [`set_raw_mode`]),
+/// since keys can't be read one by one without it.
+pub(crate) struct RawModeGuard {
+    was_raw_mode_enabled: bool,
+    hid_cursor: bool,
+}
+
+impl RawModeGuard {
+    /// Enables raw mode if it isn't already.
+    pub(crate) fn enable() -> crossterm::Result<Self> {
+        let was_raw_mode_enabled = is_raw_mode_enabled()?;
+
+        if !was_raw_mode_enabled {
+            enable_raw_mode()?;
+        }
+
+        Ok(Self {
+            was_raw_mode_enabled,
+            hid_cursor: false,
+        })
+    }
+
+    /// Hides the cursor until the guard is dropped.
+    pub(crate) fn hide_cursor(&mut self) -> crossterm::Result<()> {
+        self.hid_cursor = true;
+
+        execute!(output(), Hide)
+    }
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        if self.hid_cursor {
+            let _ = execute!(output(), Show);
+        }
+
+        if !self.was_raw_mode_enabled {
+            let _ = disable_raw_mode();
+        }
+    }
+}
+
+/// Returns `true` while a session keeps raw mode enabled.
+pub(crate) fn is_active() -> bool {
+    ACTIVE.with(Cell::get)
+}
+
+/// Enables raw mode if it isn't already, returning `false` if that fails.
+///
+/// Errors are kept to be returned by the output, see [`output::fail`].
+pub(crate) fn acquire_raw_mode() -> bool {
+    let result =
+        is_raw_mode_enabled().and_then(|enabled| if enabled { Ok(()) } else { enable_raw_mode() });
+
+    result.map_err(output::fail).is_ok()
+}
+
+/// Disables raw mode if it's enabled, unless a session keeps it enabled.
+///
+/// Errors are kept to be returned by the output, see [`output::fail`].
+pub(crate) fn release_raw_mode() {
+    if is_active() {
+        return;
+    }
+
+    let result =
+        is_raw_mode_enabled().and_then(|enabled| if enabled { disable_raw_mode() } else { Ok(()) });
+
+    if let Err(error) = result {
+        output::fail(error);
+    }
+}
@@ -0,0 +1,82 @@
+//! The function used to sleep between characters, chosen at runtime.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::thread;
+use std::time::Duration;
+
+static SLEEPER: AtomicU8 = AtomicU8::new(Sleeper::DEFAULT as u8);
+
+/// How clp sleeps, whether it's between typed characters or in [`WaitFor`](crate::WaitFor).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sleeper {
+    /// The standard library's [`sleep`](thread::sleep) function,
+    /// which is fine on most platforms but notoriously inaccurate on Windows.
+    Standard,
+    /// The [`spin_sleep`](https://docs.rs/spin_sleep/) crate's [`sleep`](spin_sleep::sleep) function,
+    /// which spins for the last part of the duration to be more accurate.
+    ///
+    /// Only available with the `spin_sleep` feature.
+    #[cfg(feature = "spin_sleep")]
+    Accurate,
+}
+
+impl Sleeper {
+    /// The sleeper used until [`set_sleeper`] is called,
+    /// which is [`Sleeper::Accurate`] since the `spin_sleep` feature is enabled.
+    #[cfg(feature = "spin_sleep")]
+    pub const DEFAULT: Self = Self::Accurate;
+
+    /// The sleeper used until [`set_sleeper`] is called,
+    /// which is [`Sleeper::Standard`] since the `spin_sleep` feature isn't enabled.
+    #[cfg(not(feature = "spin_sleep"))]
+    pub const DEFAULT: Self = Self::Standard;
+
+    /// Sleeps for `duration` with this sleeper.
+    pub fn sleep(self, duration: Duration) {
+        match self {
+            Self::Standard => thread::sleep(duration),
+            #[cfg(feature = "spin_sleep")]
+            Self::Accurate => spin_sleep::sleep(duration),
+        }
+    }
+}
+
+impl Default for Sleeper {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Sets how clp sleeps from now on, for example to sleep accurately only on Windows.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "spin_sleep")]
+/// # {
+/// use clp::{set_sleeper, Sleeper};
+///
+/// set_sleeper(if cfg!(windows) {
+///     Sleeper::Accurate
+/// } else {
+///     Sleeper::Standard
+/// });
+/// # }
+/// ```
+pub fn set_sleeper(sleeper: Sleeper) {
+    SLEEPER.store(sleeper as u8, Ordering::Relaxed);
+}
+
+/// Returns how clp currently sleeps.
+pub fn sleeper() -> Sleeper {
+    match SLEEPER.load(Ordering::Relaxed) {
+        #[cfg(feature = "spin_sleep")]
+        value if value == Sleeper::Accurate as u8 => Sleeper::Accurate,
+        _ => Sleeper::Standard,
+    }
+}
+
+/// Sleeps for `duration` with the current [`Sleeper`].
+pub(crate) fn sleep(duration: Duration) {
+    sleeper().sleep(duration);
+}
@@ -0,0 +1,183 @@
+//! Single slides that can be shown or rendered on their own.
+
+use crate::clock::SlideEnd;
+use crate::output::{self, output, Output};
+use crate::{session, text, TypewriterPrint, Typing, WaitForInteraction};
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::{Command, QueueableCommand};
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+/// A slide, usually a closure that invokes [`slide`](crate::slide), that can be shown or rendered on its own.
+///
+/// Rendering a slide to a string makes it testable,
+/// for example by comparing it against a snapshot to catch accidental changes to its content.
+///
+/// # Examples
+///
+/// ```
+/// use clp::{slide, Slide, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// let greeting = Slide::new(|| slide!(TypewriterPrint("Hello, world!", Duration::from_millis(25))));
+///
+/// assert_eq!(
+///     greeting.render_to_string(false).expect("the slide should render"),
+///     "Hello, world!",
+/// );
+/// ```
+pub struct Slide {
+    render: Box<dyn Fn() -> crossterm::Result<()>>,
+}
+
+impl Slide {
+    /// Creates a slide that's rendered by `render`.
+    pub fn new(render: impl Fn() -> crossterm::Result<()> + 'static) -> Self {
+        Self {
+            render: Box::new(render),
+        }
+    }
+
+    /// Creates a builder for a slide made of commands added one by one,
+    /// which is useful for slides built from data rather than written out with [`slide`](crate::slide).
+    pub fn builder() -> SlideBuilder {
+        SlideBuilder::default()
+    }
+
+    /// Shows the slide.
+    pub fn show(&self) -> crossterm::Result<()> {
+        (self.render)()
+    }
+
+    /// Renders the slide without delays or interactions and returns what it printed.
+    ///
+    /// The output doesn't depend on timing or on the terminal,
+    /// which is assumed to be 80 columns wide and 24 rows tall.
+    /// If `ansi` is `false`, escape sequences (like colors and cursor movements) are left out,
+    /// leaving only the text.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the slide, if it returns one.
+    pub fn render_to_string(&self, ansi: bool) -> crossterm::Result<String> {
+        render_to_string(&self.render, ansi)
+    }
+}
+
+/// A command of a [`SlideBuilder`], which queues it for execution.
+type QueueFn = Box<dyn Fn(&mut Output) -> io::Result<()>>;
+
+/// A slide made of commands added one by one, see [`Slide::builder`].
+///
+/// Like with [`slide`](crate::slide), the screen is cleared before the commands are executed,
+/// and the slide waits for an interaction after them.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::crossterm::style::Print;
+/// use clp::Slide;
+/// use std::time::Duration;
+///
+/// let points = ["Fast", "Reliable", "Productive"];
+/// let mut builder = Slide::builder().typewriter("Why Rust?\n", Duration::from_millis(25));
+///
+/// for point in points {
+///     builder = builder.wait().command(Print(format!("- {point}\n")));
+/// }
+///
+/// assert_eq!(builder.len(), 7);
+/// builder.show().expect("the points should be revealed one by one");
+/// ```
+#[derive(Default)]
+pub struct SlideBuilder {
+    commands: Vec<QueueFn>,
+}
+
+impl SlideBuilder {
+    /// Adds a command to the end of the slide.
+    pub fn command(mut self, command: impl Command + 'static) -> Self {
+        self.commands
+            .push(Box::new(move |output| output.queue(&command).map(|_| ())));
+        self
+    }
+
+    /// Adds text to the end of the slide, typed like [`TypewriterPrint`].
+    pub fn typewriter(self, text: impl Display, typing: impl Into<Typing>) -> Self {
+        self.command(TypewriterPrint(text.to_string(), typing.into()))
+    }
+
+    /// Adds a wait for an interaction to the end of the slide, like [`WaitForInteraction`].
+    ///
+    /// The slide already waits for an interaction after its last command, so this is only needed between them.
+    pub fn wait(self) -> Self {
+        self.command(WaitForInteraction)
+    }
+
+    /// Returns the number of commands added to the slide, including its waits.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns `true` if no commands have been added to the slide.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Shows the slide, like [`slide`](crate::slide) does with the same commands.
+    pub fn show(&self) -> crossterm::Result<()> {
+        let _slide_end = SlideEnd;
+        let _session = session::start();
+        let mut output = output();
+
+        output.queue(Clear(ClearType::All))?;
+
+        for command in &self.commands {
+            command(&mut output)?;
+        }
+
+        output.queue(WaitForInteraction)?;
+        output.flush()
+    }
+
+    /// Turns the builder into a [`Slide`], which can be rendered to a string or added to a presentation.
+    pub fn build(self) -> Slide {
+        Slide::new(move || self.show())
+    }
+}
+
+/// Renders a slide like [`Slide::render_to_string`] does.
+pub(crate) fn render_to_string(
+    render: &dyn Fn() -> crossterm::Result<()>,
+    ansi: bool,
+) -> crossterm::Result<String> {
+    let rendered = Capture::default();
+
+    output::render_headless(Box::new(rendered.clone()), render)?;
+
+    let rendered = String::from_utf8_lossy(&rendered.0.borrow()).into_owned();
+
+    if ansi {
+        return Ok(rendered);
+    }
+
+    Ok(text::strip_escapes(&rendered))
+}
+
+/// A stream that keeps everything written to it.
+#[derive(Clone, Default)]
+struct Capture(Rc<RefCell<Vec<u8>>>);
+
+impl Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
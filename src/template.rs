@@ -0,0 +1,104 @@
+//! Slide layouts that are defined once and filled in per slide.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::rc::Rc;
+
+/// A function that lays out a slide from the contents of the slots of a [`Template`].
+type LayoutFn = Rc<dyn Fn(&HashMap<String, String>) -> crossterm::Result<()>>;
+
+/// A slide layout with named slots, that's defined once and rendered with different contents for each slot.
+///
+/// The layout is given the contents of every slot by name, so `slots["title"]` is the content of the `title` slot.
+/// Cloning a template is cheap, since clones share the same layout.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::crossterm::style::Stylize;
+/// use clp::{slide, Presentation, Template, TypewriterPrint, TypewriterPrintStyledContent};
+/// use std::time::Duration;
+///
+/// let section = Template::new(["title", "body"], |slots| {
+///     slide!(
+///         TypewriterPrintStyledContent(slots["title"].as_str().bold(), Duration::from_millis(25)),
+///         TypewriterPrint(format!("\n\n{}", slots["body"]), Duration::from_millis(25)),
+///     )
+/// });
+/// let first = section.clone();
+/// let second = section.clone();
+///
+/// Presentation::new()
+///     .slide(move || first.render([("title", "Background"), ("body", "How we got here.")]))
+///     .slide(move || second.render([("title", "Results"), ("body", "What we found.")]))
+///     .run()
+///     .expect("the presentation should run");
+/// ```
+#[derive(Clone)]
+pub struct Template {
+    slots: Vec<String>,
+    layout: LayoutFn,
+}
+
+impl Template {
+    /// Creates a template with the given slots, that's laid out by `layout`.
+    pub fn new<S: Into<String>>(
+        slots: impl IntoIterator<Item = S>,
+        layout: impl Fn(&HashMap<String, String>) -> crossterm::Result<()> + 'static,
+    ) -> Self {
+        Self {
+            slots: slots.into_iter().map(Into::into).collect(),
+            layout: Rc::new(layout),
+        }
+    }
+
+    /// Returns the names of the slots of the template.
+    pub fn slots(&self) -> &[String] {
+        &self.slots
+    }
+
+    /// Lays out a slide with the given content for each slot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a slot of the template isn't filled in,
+    /// if a slot that the template doesn't have is filled in,
+    /// or if the layout returns an error.
+    pub fn render<S: Into<String>, C: fmt::Display>(
+        &self,
+        fills: impl IntoIterator<Item = (S, C)>,
+    ) -> crossterm::Result<()> {
+        let mut contents = HashMap::new();
+
+        for (slot, content) in fills {
+            let slot = slot.into();
+
+            if !self.slots.contains(&slot) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("the template has no slot named \"{slot}\""),
+                ));
+            }
+
+            contents.insert(slot, content.to_string());
+        }
+
+        if let Some(missing) = self.slots.iter().find(|slot| !contents.contains_key(*slot)) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("the \"{missing}\" slot of the template isn't filled in"),
+            ));
+        }
+
+        (self.layout)(&contents)
+    }
+}
+
+impl fmt::Debug for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Template")
+            .field("slots", &self.slots)
+            .finish_non_exhaustive()
+    }
+}
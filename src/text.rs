@@ -0,0 +1,121 @@
+//! Preprocessing of the text printed by the typewriter commands.
+
+use crate::markers::Segment;
+use crate::output;
+use crossterm::terminal::is_raw_mode_enabled;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static NORMALIZE_NEWLINES: AtomicBool = AtomicBool::new(true);
+
+/// Sets whether the typewriter commands normalize line endings, which they do by default.
+///
+/// When enabled, `\r\n` and lone `\r` are printed as `\n`,
+/// so text authored on any operating system renders the same way.
+/// Disable it if your text contains carriage returns that should be printed literally.
+pub fn set_newline_normalization(enabled: bool) {
+    NORMALIZE_NEWLINES.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether the typewriter commands normalize line endings.
+pub fn newline_normalization() -> bool {
+    NORMALIZE_NEWLINES.load(Ordering::Relaxed)
+}
+
+/// Replaces `\r\n` and lone `\r` in `text` with `\n`, if newline normalization is enabled.
+pub(crate) fn normalize_newlines(text: &str) -> Cow<'_, str> {
+    if !newline_normalization() || !text.contains('\r') {
+        return Cow::Borrowed(text);
+    }
+
+    Cow::Owned(text.replace("\r\n", "\n").replace('\r', "\n"))
+}
+
+/// Moves ANSI escape sequences out of the text segments into raw segments,
+/// so they're written all at once instead of being typed and delayed on.
+///
+/// This lets the typewriter commands print text that was already styled by other crates.
+/// Control sequences (`ESC [ ...`), operating system commands (`ESC ] ...`, ended by `BEL` or `ESC \\`)
+/// and two-character escape sequences are recognized.
+pub(crate) fn split_escapes(segments: Vec<Segment>) -> Vec<Segment> {
+    let mut split = Vec::with_capacity(segments.len());
+
+    for segment in segments {
+        let Segment::Text(text) = segment else {
+            split.push(segment);
+            continue;
+        };
+        let mut rest = text.as_str();
+
+        while let Some(start) = rest.find('\x1b') {
+            if start > 0 {
+                split.push(Segment::Text(rest[..start].to_string()));
+            }
+
+            let length = escape_length(&rest[start..]);
+
+            split.push(Segment::Raw(rest[start..start + length].to_string()));
+            rest = &rest[start + length..];
+        }
+
+        if !rest.is_empty() {
+            split.push(Segment::Text(rest.to_string()));
+        }
+    }
+
+    split
+}
+
+/// Returns the length in bytes of the escape sequence at the start of `text`, which starts with `ESC`.
+fn escape_length(text: &str) -> usize {
+    let mut chars = text.char_indices().skip(1);
+
+    match chars.next() {
+        Some((_, '[')) => chars
+            .find(|(_, char)| ('\x40'..='\x7e').contains(char))
+            .map_or(text.len(), |(index, char)| index + char.len_utf8()),
+        Some((_, ']')) => {
+            let mut previous = '\0';
+
+            chars
+                .find(|&(_, char)| {
+                    let ends = char == '\x07' || (previous == '\x1b' && char == '\\');
+
+                    previous = char;
+                    ends
+                })
+                .map_or(text.len(), |(index, char)| index + char.len_utf8())
+        }
+        Some((index, char)) => index + char.len_utf8(),
+        None => text.len(),
+    }
+}
+
+/// Returns `text` without its escape sequences, leaving only what's printed.
+pub(crate) fn strip_escapes(text: &str) -> String {
+    split_escapes(vec![Segment::Text(text.to_string())])
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Text(content) => Some(content),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Writes a line break that returns to the first column, even in raw mode.
+///
+/// In raw mode, a bare `\n` only moves the cursor down, so `\r\n` is written instead.
+/// While rendering headlessly, `\n` is always written, so the output doesn't depend on the terminal.
+pub(crate) fn write_newline(f: &mut impl fmt::Write) -> fmt::Result {
+    if output::is_headless() {
+        return f.write_char('\n');
+    }
+
+    // `\r\n` returns to the first column either way, so it's written if raw mode can't be checked.
+    if is_raw_mode_enabled().unwrap_or(true) {
+        f.write_str("\r\n")
+    } else {
+        f.write_char('\n')
+    }
+}
@@ -0,0 +1,195 @@
+//! Text that's word-wrapped inside a bordered box.
+
+use crate::layout::{self, with_content_width};
+use crate::typing::typewrite;
+use crate::{text, Typing, WaitForInteraction};
+use crossterm::cursor::{MoveToColumn, MoveToNextLine, MoveToPreviousLine};
+use crossterm::style::Print;
+use crossterm::Command;
+use std::fmt::{self, Display};
+
+/// What a [`TextBox`] does with content that doesn't fit inside it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    /// Cuts the content off, ending the last line that fits with `…`.
+    #[default]
+    Truncate,
+    /// Splits the content into pages that fit, waiting for an interaction before replacing one with the next.
+    Paginate,
+}
+
+/// A command that draws a box with a border, then types content word-wrapped inside it.
+///
+/// [`width`](TextBox::width) and [`height`](TextBox::height) include the border,
+/// and there's a column of space between the border and the content on each side.
+/// The box is drawn from the cursor's position, and the cursor ends up on the line after it.
+/// Wide characters take up two columns, and are never split across lines.
+/// If the box doesn't fit between the cursor's column and the right edge of the terminal,
+/// or the terminal is shorter than the box, a message saying so is printed instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, Overflow, TextBox};
+///
+/// let mut text_box = TextBox::new(
+///     "A box is the building block of card-style layouts. \
+///      Content that doesn't fit is split into pages.",
+///     30,
+///     5,
+/// );
+///
+/// text_box.overflow = Overflow::Paginate;
+///
+/// slide!(text_box).expect("the content should be typed inside the box");
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution
+/// (which [`TextBox`] is when in [`slide`](crate::slide))
+/// otherwise they do nothing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextBox<T: Display> {
+    /// The content typed inside the box.
+    pub content: T,
+    /// The number of columns the box takes up, including its border.
+    pub width: u16,
+    /// The number of rows the box takes up, including its border.
+    pub height: u16,
+    /// How the content is typed.
+    pub typing: Typing,
+    /// What's done with content that doesn't fit inside the box.
+    pub overflow: Overflow,
+}
+
+impl<T: Display> TextBox<T> {
+    /// Creates a box that truncates its content, typed with the slide's typing options (see [`Typing::inherit`]).
+    pub fn new(content: T, width: u16, height: u16) -> Self {
+        Self {
+            content,
+            width,
+            height,
+            typing: Typing::inherit(),
+            overflow: Overflow::Truncate,
+        }
+    }
+
+    /// Returns the smallest number of columns and rows the terminal needs for the box, which is its size.
+    pub fn minimum_size(&self) -> (u16, u16) {
+        (self.width.max(5), self.height.max(3))
+    }
+
+    /// Returns the lines of each page of content, each at most `inner_width` columns wide.
+    fn pages(&self, inner_width: usize, inner_height: usize) -> Vec<Vec<String>> {
+        let content = text::normalize_newlines(&self.content.to_string()).into_owned();
+        let wrapped = layout::wrap(content.trim_end_matches('\n'), inner_width, 0);
+        let lines: Vec<String> = wrapped
+            .split('\n')
+            .map(|line| line.trim_end().to_string())
+            .collect();
+
+        match self.overflow {
+            Overflow::Paginate => lines.chunks(inner_height).map(<[String]>::to_vec).collect(),
+            Overflow::Truncate => {
+                let mut page = lines.into_iter().take(inner_height + 1).collect::<Vec<_>>();
+
+                if page.len() > inner_height {
+                    page.pop();
+
+                    if let Some(last) = page.last_mut() {
+                        while !last.is_empty() && layout::width(last) + 1 > inner_width {
+                            last.pop();
+                        }
+
+                        last.push('…');
+                    }
+                }
+
+                vec![page]
+            }
+        }
+    }
+}
+
+/// Moves the cursor from `row` to `to`, counting rows from the top of the box, then to `column`.
+fn move_to(f: &mut impl fmt::Write, row: &mut u16, to: u16, column: u16) -> fmt::Result {
+    if to > *row {
+        MoveToNextLine(to - *row).write_ansi(f)?;
+    } else if to < *row {
+        MoveToPreviousLine(*row - to).write_ansi(f)?;
+    }
+
+    *row = to;
+
+    MoveToColumn(column).write_ansi(f)
+}
+
+impl<T: Display> Command for TextBox<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let (width, height) = self.minimum_size();
+        let column = layout::current_column();
+
+        // A box that's too tall scrolls the terminal while it's drawn,
+        // so the content would be typed over the wrong rows.
+        if layout::write_too_small(f, column.saturating_add(width), height)? {
+            return text::write_newline(f);
+        }
+
+        let inner_width = (width - 4) as usize;
+        let inner_height = height - 2;
+        let border = "─".repeat(width as usize - 2);
+
+        Print(format!("┌{border}┐")).write_ansi(f)?;
+        text::write_newline(f)?;
+
+        for _ in 0..inner_height {
+            MoveToColumn(column).write_ansi(f)?;
+            Print(format!("│{}│", " ".repeat(width as usize - 2))).write_ansi(f)?;
+            text::write_newline(f)?;
+        }
+
+        MoveToColumn(column).write_ansi(f)?;
+        Print(format!("└{border}┘")).write_ansi(f)?;
+        text::write_newline(f)?;
+
+        let after = inner_height + 2;
+        let mut row = after;
+
+        // The content is already wrapped to the box, so it shouldn't be wrapped and centered again while typing.
+        with_content_width(None, || {
+            for (index, page) in self
+                .pages(inner_width, inner_height as usize)
+                .iter()
+                .enumerate()
+            {
+                if index > 0 {
+                    move_to(f, &mut row, after, 0)?;
+                    WaitForInteraction.write_ansi(f)?;
+
+                    for blank in 1..=inner_height {
+                        move_to(f, &mut row, blank, column + 2)?;
+                        Print(" ".repeat(inner_width)).write_ansi(f)?;
+                    }
+                }
+
+                for (offset, line) in page.iter().enumerate() {
+                    move_to(f, &mut row, offset as u16 + 1, column + 2)?;
+                    typewrite(f, line, None, self.typing)?;
+                }
+            }
+
+            move_to(f, &mut row, after, 0)
+        })
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        panic!("tried to execute TextBox command using WinAPI, use ANSI instead");
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
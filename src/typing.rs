@@ -0,0 +1,1132 @@
+//! The typing loop shared by all typewriter commands, and the options that control it.
+
+use crate::markers::{self, Segment};
+use crate::output::{self, output};
+use crate::sleeper::sleep;
+use crate::{clock, estimate, interaction, layout, random, runner, session, text};
+use crossterm::cursor::{MoveLeft, MoveRight};
+use crossterm::event::{self, Event};
+use crossterm::style::{ContentStyle, PrintStyledContent, StyledContent};
+use crossterm::Command;
+use std::cell::Cell;
+use std::env;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::io;
+use std::io::Write as _;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+use std::{mem, ptr};
+use unicode_segmentation::UnicodeSegmentation;
+
+thread_local! {
+    static PROFILE: Cell<Option<Typing>> = const { Cell::new(None) };
+}
+
+/// Options that control how a typewriter command types its text.
+///
+/// A plain [`Duration`] converts into [`Typing`] with that delay and otherwise default options,
+/// which is why `TypewriterPrint(text, Duration::from_millis(25))` works.
+///
+/// Options that aren't set fall back to those of the slide's profile, if it has one
+/// (see [`Presentation::profile`](crate::Presentation::profile)), and then to their defaults.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{slide, TypewriterPrint, Typing};
+/// use std::time::Duration;
+///
+/// slide!(TypewriterPrint(
+///     "Hello, world!",
+///     Typing::new(Duration::from_millis(25)).right_to_left(),
+/// ))
+/// .expect("\"Hello, world!\" should be typed from its last character to its first");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Typing {
+    delay: Option<Duration>,
+    direction: Option<Direction>,
+    easing: Option<(Duration, Duration, Easing)>,
+    punctuation_pause: Option<(f32, f32)>,
+    whitespace: Option<Whitespace>,
+    granularity: Option<Granularity>,
+    jitter: Option<Duration>,
+    bell: Option<(usize, bool)>,
+    flush_interval: Option<Duration>,
+}
+
+impl Typing {
+    /// The delay between characters when neither the options nor the slide's profile set one.
+    pub const DEFAULT_DELAY: Duration = Duration::from_millis(25);
+
+    /// Creates typing options with the given delay between characters.
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay: Some(delay),
+            ..Self::default()
+        }
+    }
+
+    /// Creates typing options that take everything, including the delay, from the slide's profile.
+    pub fn inherit() -> Self {
+        Self::default()
+    }
+
+    /// Returns the delay between characters.
+    pub fn delay(&self) -> Duration {
+        self.delay.unwrap_or(Self::DEFAULT_DELAY)
+    }
+
+    /// Returns the direction that lines are typed in.
+    pub fn direction(&self) -> Direction {
+        self.direction.unwrap_or_default()
+    }
+
+    /// Sets the direction that lines are typed in.
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// Types each line from its last character to its first, see [`Direction::RightToLeft`].
+    pub fn right_to_left(self) -> Self {
+        self.with_direction(Direction::RightToLeft)
+    }
+
+    /// Returns the delays at the start and end of the text, and the easing between them, if they're set.
+    pub fn easing(&self) -> Option<(Duration, Duration, Easing)> {
+        self.easing
+    }
+
+    /// Changes the delay between characters from `start` at the first character to `end` at the last,
+    /// following `easing`.
+    ///
+    /// This takes the place of the delay set with [`Typing::new`].
+    pub fn with_easing(mut self, start: Duration, end: Duration, easing: Easing) -> Self {
+        self.easing = Some((start, end, easing));
+        self
+    }
+
+    /// Returns the delay multipliers applied after commas and after sentences, `(1.0, 1.0)` by default.
+    pub fn punctuation_pause(&self) -> (f32, f32) {
+        self.punctuation_pause.unwrap_or((1.0, 1.0))
+    }
+
+    /// Multiplies the delay after `,`, `;` and `:` by `comma`, and after `.`, `!`, `?` and line breaks by `sentence`.
+    ///
+    /// Only the first of consecutive punctuation marks is paused after for longer,
+    /// so `...`, `?!` or a line break after a full stop don't add up to an absurdly long pause.
+    /// Negative and non-finite multipliers are treated as `1.0`.
+    pub fn with_punctuation_pause(mut self, comma: f32, sentence: f32) -> Self {
+        let sanitize = |multiplier: f32| {
+            if multiplier.is_finite() && multiplier >= 0.0 {
+                multiplier
+            } else {
+                1.0
+            }
+        };
+
+        self.punctuation_pause = Some((sanitize(comma), sanitize(sentence)));
+        self
+    }
+
+    /// Returns how whitespace is typed.
+    pub fn whitespace(&self) -> Whitespace {
+        self.whitespace.unwrap_or_default()
+    }
+
+    /// Sets how whitespace is typed.
+    pub fn with_whitespace(mut self, whitespace: Whitespace) -> Self {
+        self.whitespace = Some(whitespace);
+        self
+    }
+
+    /// Returns whether text is typed one character, word or line at a time.
+    pub fn granularity(&self) -> Granularity {
+        self.granularity.unwrap_or_default()
+    }
+
+    /// Sets whether text is typed one character, word or line at a time.
+    pub fn with_granularity(mut self, granularity: Granularity) -> Self {
+        self.granularity = Some(granularity);
+        self
+    }
+
+    /// Types text one word at a time, see [`Granularity::Words`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, TypewriterPrint, Typing};
+    /// use std::time::Duration;
+    ///
+    /// slide!(TypewriterPrint(
+    ///     "Long paragraphs are quicker to read when they appear one word at a time.",
+    ///     Typing::new(Duration::from_millis(150)).by_words(),
+    /// ))
+    /// .expect("the paragraph should be typed one word at a time");
+    /// ```
+    pub fn by_words(self) -> Self {
+        self.with_granularity(Granularity::Words)
+    }
+
+    /// Types text one line at a time, see [`Granularity::Lines`].
+    pub fn by_lines(self) -> Self {
+        self.with_granularity(Granularity::Lines)
+    }
+
+    /// Returns how much the delay after each character can randomly differ from what it would be otherwise,
+    /// which is [`Duration::ZERO`] by default.
+    pub fn jitter(&self) -> Duration {
+        self.jitter.unwrap_or_default()
+    }
+
+    /// Randomly lengthens or shortens the delay after each character by up to `jitter`, for a more human feel.
+    ///
+    /// A delay is never shortened to less than a tenth of what it would be otherwise,
+    /// so characters with a delay still have one, and those without one, like instant whitespace, still don't.
+    /// The randomness can be made the same every time with [`seed_random`](crate::seed_random).
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = Some(jitter);
+        self
+    }
+
+    /// Returns how many characters are typed between rings of the terminal bell,
+    /// and whether whitespace counts as a character for it, if the bell rings at all.
+    pub fn bell_every(&self) -> Option<(usize, bool)> {
+        self.bell
+    }
+
+    /// Rings the terminal bell after every `every` characters, like the click of a typewriter's keys.
+    ///
+    /// If `whitespace` is `false`, whitespace doesn't ring the bell or count towards `every`,
+    /// since constant beeping is obnoxious.
+    /// The bell doesn't ring while rendering headlessly, while restoring a slide or for instantly printed text.
+    /// For other sounds, use [`typewrite_with_callback`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, TypewriterPrint, Typing};
+    /// use std::time::Duration;
+    ///
+    /// slide!(TypewriterPrint(
+    ///     "Click, click, click.",
+    ///     Typing::new(Duration::from_millis(80)).with_bell_every(3, false),
+    /// ))
+    /// .expect("the bell should ring after every third character");
+    /// ```
+    pub fn with_bell_every(mut self, every: usize, whitespace: bool) -> Self {
+        self.bell = Some((every, whitespace));
+        self
+    }
+
+    /// Returns how often typed characters are flushed to the terminal, if they aren't flushed after every character.
+    pub fn flush_interval(&self) -> Option<Duration> {
+        self.flush_interval
+    }
+
+    /// Flushes typed characters to the terminal at most once every `interval` instead of after every character.
+    ///
+    /// Over high-latency connections like SSH, flushing after every character makes typing jerky,
+    /// so flushing on a timer, like every 16 milliseconds for about 60 frames per second, smooths it out.
+    /// The delays between characters stay the same, characters just show up in small groups.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use clp::{slide, TypewriterPrint, Typing};
+    /// use std::time::Duration;
+    ///
+    /// slide!(TypewriterPrint(
+    ///     "Hello from the other side of the world!",
+    ///     Typing::new(Duration::from_millis(5)).with_flush_interval(Duration::from_millis(16)),
+    /// ))
+    /// .expect("\"Hello from the other side of the world!\" should be typed in groups of characters");
+    /// ```
+    pub fn with_flush_interval(mut self, interval: Duration) -> Self {
+        self.flush_interval = Some(interval);
+        self
+    }
+
+    /// Fills in the options that aren't set with those of `fallback`.
+    pub fn or(self, fallback: Typing) -> Self {
+        Self {
+            delay: self.delay.or(fallback.delay),
+            direction: self.direction.or(fallback.direction),
+            easing: self.easing.or(fallback.easing),
+            punctuation_pause: self.punctuation_pause.or(fallback.punctuation_pause),
+            whitespace: self.whitespace.or(fallback.whitespace),
+            granularity: self.granularity.or(fallback.granularity),
+            jitter: self.jitter.or(fallback.jitter),
+            bell: self.bell.or(fallback.bell),
+            flush_interval: self.flush_interval.or(fallback.flush_interval),
+        }
+    }
+
+    /// Returns `true` if there's no delay between characters at all, so the text can be printed instantly.
+    fn is_instant(&self) -> bool {
+        match self.easing {
+            Some((start, end, _)) => start.is_zero() && end.is_zero(),
+            None => self.delay().is_zero(),
+        }
+    }
+
+    /// Returns the delay between characters at `progress`, from `0.0` at the first character to `1.0` at the last.
+    fn delay_at(&self, progress: f32) -> Duration {
+        let Some((start, end, easing)) = self.easing else {
+            return self.delay();
+        };
+        let eased = easing.apply(progress.clamp(0.0, 1.0));
+        let seconds = start.as_secs_f32() + (end.as_secs_f32() - start.as_secs_f32()) * eased;
+
+        Duration::try_from_secs_f32(seconds).unwrap_or(Duration::ZERO)
+    }
+
+    /// Returns how long to wait after typing `char`, which was typed right after `previous`, at `progress`.
+    fn delay_after(&self, char: char, previous: char, progress: f32) -> Duration {
+        let (comma, sentence) = self.punctuation_pause();
+        let delay = self.delay_at(progress);
+        let (char, previous) = match self.granularity() {
+            Granularity::Characters => (char, previous),
+            // The only delay is after the whitespace that ends a word, and it's paused for like the word's last character.
+            Granularity::Words if char.is_whitespace() && !previous.is_whitespace() => {
+                (previous, ' ')
+            }
+            Granularity::Words => return Duration::ZERO,
+            Granularity::Lines if char == '\n' => return delay,
+            Granularity::Lines => return Duration::ZERO,
+        };
+
+        match char {
+            _ if is_punctuation_pause(char) && is_punctuation_pause(previous) => delay,
+            ',' | ';' | ':' => delay.mul_f32(comma),
+            '.' | '!' | '?' | '\n' => delay.mul_f32(sentence),
+            _ if char.is_whitespace() && self.whitespace() == Whitespace::Instant => Duration::ZERO,
+            _ => delay,
+        }
+    }
+}
+
+/// Randomly lengthens or shortens `delay` by up to `jitter`, but never to less than a tenth of it.
+fn jitter(delay: Duration, jitter: Duration) -> Duration {
+    if delay.is_zero() || jitter.is_zero() {
+        return delay;
+    }
+
+    let range = u64::try_from(jitter.as_nanos()).unwrap_or(u64::MAX / 2);
+    let offset = random::next_u64() % (range * 2 + 1);
+    let jittered = (delay + Duration::from_nanos(offset)).saturating_sub(jitter);
+
+    jittered.max(delay / 10)
+}
+
+/// Returns `true` if the delay after `char` is multiplied by the punctuation pause.
+fn is_punctuation_pause(char: char) -> bool {
+    matches!(char, ',' | ';' | ':' | '.' | '!' | '?' | '\n')
+}
+
+impl From<Duration> for Typing {
+    fn from(delay: Duration) -> Self {
+        Self::new(delay)
+    }
+}
+
+/// How the delay between characters changes over the course of the text, see [`Typing::with_easing`].
+///
+/// Each easing maps the progress through the text, from `0.0` to `1.0`,
+/// to how far the delay has changed from its start to its end, also from `0.0` to `1.0`.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Easing {
+    /// Changes the delay at a constant rate.
+    #[default]
+    Linear,
+    /// Changes the delay slowly at first, then quickly.
+    EaseIn,
+    /// Changes the delay quickly at first, then slowly.
+    EaseOut,
+    /// Changes the delay slowly at first and last, and quickly in the middle.
+    EaseInOut,
+    /// Changes the delay according to a custom function.
+    Custom(fn(f32) -> f32),
+}
+
+impl Easing {
+    /// Maps `progress` through the text to how far the delay has changed.
+    pub fn apply(self, progress: f32) -> f32 {
+        match self {
+            Self::Linear => progress,
+            Self::EaseIn => progress * progress,
+            Self::EaseOut => progress * (2.0 - progress),
+            Self::EaseInOut if progress < 0.5 => 2.0 * progress * progress,
+            Self::EaseInOut => 1.0 - 2.0 * (1.0 - progress) * (1.0 - progress),
+            Self::Custom(easing) => easing(progress),
+        }
+    }
+}
+
+impl PartialEq for Easing {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Custom(easing), Self::Custom(other)) => ptr::fn_addr_eq(*easing, *other),
+            _ => mem::discriminant(self) == mem::discriminant(other),
+        }
+    }
+}
+
+/// How a typewriter command types whitespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Whitespace {
+    /// Whitespace is typed like any other character.
+    #[default]
+    Typed,
+    /// Whitespace, other than line breaks, is printed without a delay.
+    Instant,
+}
+
+/// How much of its text a typewriter command types at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Granularity {
+    /// Text is typed one character at a time.
+    #[default]
+    Characters,
+    /// Text is typed one word at a time, each word along with the whitespace right after it,
+    /// so the delay is the time between words rather than between characters.
+    ///
+    /// Line breaks and runs of spaces are kept as they are,
+    /// and the punctuation pause applies to words ending with punctuation.
+    Words,
+    /// Text is typed one line at a time, each line along with the line break after it,
+    /// so the delay is the time between lines rather than between characters.
+    ///
+    /// Every line break is followed by the delay, including those of empty lines and a trailing one,
+    /// while the end of the text isn't, and the punctuation pause doesn't apply.
+    Lines,
+}
+
+/// The direction that a typewriter command types each line in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    /// Types each line from its first character to its last, like you normally would.
+    #[default]
+    LeftToRight,
+    /// Types each line from its last character to its first,
+    /// so the line fills in from the right toward the left.
+    ///
+    /// Every line is reversed independently, and ends up reading normally once it's typed.
+    RightToLeft,
+}
+
+/// The longest delay between characters that [`TypewriterPrint::try_new`](crate::TypewriterPrint::try_new)
+/// accepts, see [`set_max_typing_delay`].
+static MAX_DELAY: RwLock<Duration> = RwLock::new(Duration::from_secs(1));
+static SKIP: RwLock<TypingSkip> = RwLock::new(TypingSkip::Off);
+/// The bits of the speed multiplier, or `0` until it's been set or read from the environment.
+static SPEED: AtomicU32 = AtomicU32::new(0);
+
+/// Sets how many times faster than their delays say typewriter commands type,
+/// which is useful for rehearsing a talk quickly without editing every delay.
+///
+/// Every delay is divided by the multiplier, including pause markers and [`WaitFor`](crate::WaitFor),
+/// and so are the estimates of how long slides take, but waiting for interactions isn't affected.
+/// A huge multiplier makes delays so short that text is typed all at once.
+/// Until this is called, the multiplier is read from the `CLP_SPEED` environment variable if it's set,
+/// and is otherwise 1.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{set_speed_multiplier, slide, TypewriterPrint};
+/// use std::time::Duration;
+///
+/// set_speed_multiplier(2.0);
+///
+/// slide!(TypewriterPrint("Hello, world!", Duration::from_millis(50)))
+///     .expect("the text should be typed with 25ms between characters");
+/// ```
+///
+/// # Panics
+///
+/// Panics if `multiplier` isn't a positive number.
+pub fn set_speed_multiplier(multiplier: f32) {
+    assert!(
+        multiplier > 0.0,
+        "the speed multiplier should be positive, but it's {multiplier}"
+    );
+
+    SPEED.store(multiplier.to_bits(), Ordering::Relaxed);
+}
+
+/// Returns how many times faster than their delays say typewriter commands type, see [`set_speed_multiplier`].
+pub fn speed_multiplier() -> f32 {
+    match SPEED.load(Ordering::Relaxed) {
+        0 => {
+            // Positive floats never have all of their bits unset, so `0` can mean the multiplier hasn't been read yet.
+            let multiplier = env::var("CLP_SPEED")
+                .ok()
+                .and_then(|speed| speed.trim().parse::<f32>().ok())
+                .filter(|speed| *speed > 0.0)
+                .unwrap_or(1.0);
+
+            SPEED.store(multiplier.to_bits(), Ordering::Relaxed);
+
+            multiplier
+        }
+        bits => f32::from_bits(bits),
+    }
+}
+
+/// Returns `delay` divided by the [`speed_multiplier`].
+pub(crate) fn at_speed(delay: Duration) -> Duration {
+    match speed_multiplier() {
+        1.0 => delay,
+        multiplier => Duration::try_from_secs_f64(delay.as_secs_f64() / f64::from(multiplier))
+            .unwrap_or(Duration::MAX),
+    }
+}
+
+/// What pressing an interaction key (see [`set_advance_keys`](crate::set_advance_keys)) while text is still being typed does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TypingSkip {
+    /// Nothing happens until the slide waits for an interaction, which the key then passes.
+    #[default]
+    Off,
+    /// The rest of the slide up to its next wait is printed instantly,
+    /// and the key is used up, so the wait still needs another interaction.
+    Skip,
+    /// The rest of the slide up to its next wait is printed instantly, and the key passes that wait too.
+    SkipAndAdvance,
+}
+
+/// Sets what pressing an interaction key, like Enter or Space, while text is still being typed does,
+/// which is nothing until the slide waits by default.
+///
+/// This is useful when a slide is taking too long during a talk.
+/// Keys are only checked for between delays and while raw mode is used for typing
+/// (see [`set_raw_mode`](crate::set_raw_mode)), and any other key pressed while typing is ignored.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::{set_typing_skip, slide, TypewriterPrint, TypingSkip};
+/// use std::time::Duration;
+///
+/// set_typing_skip(TypingSkip::Skip);
+///
+/// slide!(TypewriterPrint(
+///     "This is a very long paragraph that can be skipped by pressing space.",
+///     Duration::from_millis(100),
+/// ))
+/// .expect("the paragraph should be typed, or printed all at once if space is pressed");
+/// ```
+pub fn set_typing_skip(skip: TypingSkip) {
+    *SKIP
+        .write()
+        .expect("typing skip lock should not be poisoned") = skip;
+}
+
+/// Returns what pressing an interaction key while text is still being typed does.
+pub fn typing_skip() -> TypingSkip {
+    *SKIP
+        .read()
+        .expect("typing skip lock should not be poisoned")
+}
+
+/// Skips the rest of the slide up to its next wait if a key that does so was pressed, see [`set_typing_skip`].
+fn check_skip_key() {
+    let skip = typing_skip();
+
+    if skip == TypingSkip::Off {
+        return;
+    }
+
+    loop {
+        let event =
+            event::poll(Duration::ZERO).and_then(|ready| ready.then(event::read).transpose());
+        let key = match event {
+            Ok(None) => return,
+            Ok(Some(Event::Key(key))) => key,
+            Ok(Some(_)) => continue,
+            Err(error) => return output::fail(error),
+        };
+
+        if let Some(error) = interaction::press_ctrl_c(key) {
+            return output::fail(error);
+        }
+
+        if interaction::is_advance_key(key.code) {
+            runner::skip_to_wait(skip == TypingSkip::SkipAndAdvance);
+
+            return;
+        }
+    }
+}
+
+/// Sets the longest delay between characters that
+/// [`TypewriterPrint::try_new`](crate::TypewriterPrint::try_new) accepts, which is one second by default.
+pub fn set_max_typing_delay(max: Duration) {
+    *MAX_DELAY
+        .write()
+        .expect("max typing delay lock should not be poisoned") = max;
+}
+
+/// Returns the longest delay between characters that
+/// [`TypewriterPrint::try_new`](crate::TypewriterPrint::try_new) accepts.
+pub fn max_typing_delay() -> Duration {
+    *MAX_DELAY
+        .read()
+        .expect("max typing delay lock should not be poisoned")
+}
+
+/// The error returned when a delay between characters is longer than the [`max_typing_delay`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DelayTooLong {
+    /// The delay that was given.
+    pub delay: Duration,
+    /// The longest delay that's accepted.
+    pub max: Duration,
+}
+
+impl Display for DelayTooLong {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a delay of {:?} between characters is longer than the maximum of {:?}",
+            self.delay, self.max,
+        )
+    }
+}
+
+impl Error for DelayTooLong {}
+
+/// Sets the profile that unset typing options fall back to, returning the previous profile.
+pub(crate) fn set_profile(profile: Option<Typing>) -> Option<Typing> {
+    PROFILE.with(|current| current.replace(profile))
+}
+
+/// Types `text` to [`output`](crate::output), calling `on_char` with each character right after it's printed.
+///
+/// This is an integration point for syncing something external to the typing,
+/// like keystroke sounds or a subtitle generator.
+/// Commands have to stay simple, so the callback can't be part of one, which is why this is a function instead.
+/// Pause markers and escape sequences aren't passed to `on_char`, but line breaks are.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clp::typewrite_with_callback;
+/// use std::time::Duration;
+///
+/// let mut typed = 0;
+///
+/// typewrite_with_callback("Hello, world!", Duration::from_millis(25), |_| typed += 1)
+///     .expect("\"Hello, world!\" should be typed");
+/// assert_eq!(typed, 13);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the output can't be written to.
+pub fn typewrite_with_callback(
+    text: impl Display,
+    typing: impl Into<Typing>,
+    mut on_char: impl FnMut(char),
+) -> io::Result<()> {
+    /// Writes formatted text to the output, keeping the error if there is one.
+    struct Adapter {
+        error: Option<io::Error>,
+    }
+
+    impl fmt::Write for Adapter {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            output().write_all(s.as_bytes()).map_err(|error| {
+                self.error = Some(error);
+                fmt::Error
+            })
+        }
+    }
+
+    let mut adapter = Adapter { error: None };
+    let result = typewrite_styled(
+        &mut adapter,
+        &text.to_string(),
+        |_, _| None,
+        typing.into(),
+        &mut on_char,
+    );
+
+    if result.is_err() {
+        return Err(adapter
+            .error
+            .unwrap_or_else(|| io::Error::other("text couldn't be formatted")));
+    }
+
+    output().flush()
+}
+
+/// Flushes the output, then sleeps for `duration` in raw mode so keypresses aren't echoed,
+/// unless raw mode isn't used while typing (see [`set_raw_mode`](crate::set_raw_mode)).
+///
+/// Nothing is slept while rendering headlessly, or while a slide is interrupted or fast-forwarded.
+pub(crate) fn pause(duration: Duration) {
+    let started = Instant::now();
+
+    output::flush();
+    wait(at_speed(duration), started);
+}
+
+/// Waits until `duration` has passed since `started`, without flushing the output first.
+///
+/// The time already spent since `started`, like on flushing and toggling raw mode, is taken off the wait,
+/// so short delays aren't made noticeably longer by the work around them.
+fn wait(duration: Duration, started: Instant) {
+    if output::is_headless() {
+        estimate::add_delay(duration);
+
+        return;
+    }
+
+    if runner::is_skipping_delays() {
+        return;
+    }
+
+    if session::uses_raw_mode() {
+        if !session::acquire_raw_mode() {
+            return;
+        }
+
+        check_skip_key();
+
+        if runner::is_skipping_delays() {
+            session::release_raw_mode();
+
+            return;
+        }
+    }
+
+    clock::sleep_until(started + duration, sleep);
+    session::release_raw_mode();
+}
+
+/// How far behind schedule typing can fall before it stops trying to catch up.
+const MAX_CATCH_UP: Duration = Duration::from_millis(50);
+
+/// Keeps track of how far through its text a typewriter command is, to know how long to wait after each character.
+struct Pacing<'a> {
+    typing: Typing,
+    typed: usize,
+    total: usize,
+    previous: char,
+    rung: usize,
+    on_char: &'a mut dyn FnMut(char),
+    last_flush: Instant,
+    due: Instant,
+}
+
+impl<'a> Pacing<'a> {
+    fn new(typing: Typing, segments: &[Segment], on_char: &'a mut dyn FnMut(char)) -> Self {
+        let total = segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(content) => content.chars().filter(|char| *char != '\n').count(),
+                _ => 0,
+            })
+            .sum();
+
+        Self {
+            typing,
+            typed: 0,
+            total,
+            previous: ' ',
+            rung: 0,
+            on_char,
+            last_flush: Instant::now(),
+            due: Instant::now(),
+        }
+    }
+
+    /// Returns how long to wait after typing `grapheme`, and counts its characters as typed.
+    ///
+    /// A grapheme is waited after as a whole, like the character it's based on,
+    /// so emoji sequences and characters with combining marks appear all at once.
+    fn after(&mut self, grapheme: &str) -> Duration {
+        let progress = match self.total {
+            0 | 1 => 0.0,
+            total => self.typed as f32 / (total - 1) as f32,
+        };
+        let mut chars = grapheme.chars();
+        let Some(first) = chars.next() else {
+            return Duration::ZERO;
+        };
+        let last = chars.next_back().unwrap_or(first);
+
+        for char in grapheme.chars() {
+            (self.on_char)(char);
+
+            if char != '\n' {
+                self.typed += 1;
+            }
+        }
+
+        let previous = mem::replace(&mut self.previous, last);
+
+        at_speed(jitter(
+            self.typing.delay_after(first, previous, progress),
+            self.typing.jitter(),
+        ))
+    }
+
+    /// Rings the terminal bell if `grapheme` is one the bell rings after, see [`Typing::with_bell_every`].
+    fn ring(&mut self, f: &mut impl fmt::Write, grapheme: &str) -> fmt::Result {
+        let Some((every, whitespace)) = self.typing.bell else {
+            return Ok(());
+        };
+
+        if every == 0 || !whitespace && grapheme.chars().all(char::is_whitespace) {
+            return Ok(());
+        }
+
+        self.rung += 1;
+
+        if !self.rung.is_multiple_of(every) || output::is_headless() || runner::is_skipping_delays()
+        {
+            return Ok(());
+        }
+
+        f.write_char('\x07')
+    }
+
+    /// Waits for as long as it should after typing `grapheme`, and counts its characters as typed.
+    ///
+    /// The output is flushed first, unless it was flushed more recently than the flush interval.
+    /// The wait is measured from when the previous character was due rather than from now,
+    /// so the time spent typing and flushing, and any oversleeping, is taken off the delay
+    /// and the speed matches the delay even when it's tiny.
+    fn pause_after(&mut self, grapheme: &str) {
+        let delay = self.after(grapheme);
+
+        // Without a delay, the character can't be seen before the next one anyway, so it's flushed along with it.
+        if delay.is_zero() {
+            return;
+        }
+
+        let flush = self
+            .typing
+            .flush_interval
+            .is_none_or(|interval| self.last_flush.elapsed() >= interval);
+
+        if flush {
+            output::flush();
+            self.last_flush = Instant::now();
+        }
+
+        wait(delay, self.due);
+
+        let now = Instant::now();
+
+        self.due = match self.due.checked_add(delay) {
+            // After falling far behind, like when the system stalls, catching up all at once would look like a glitch.
+            Some(due) if now.saturating_duration_since(due) < MAX_CATCH_UP => due,
+            _ => now,
+        };
+    }
+
+    /// Pauses for `duration`, like a pause marker does.
+    fn pause(&mut self, duration: Duration) {
+        pause(duration);
+        self.due = Instant::now();
+    }
+}
+
+/// Writes `text` one character at a time, as described by `typing`.
+///
+/// Line endings are normalized (see [`set_newline_normalization`](crate::set_newline_normalization)),
+/// pause markers (see [`MarkerSyntax`](crate::MarkerSyntax)) are consumed rather than printed,
+/// ANSI escape sequences already in the text are written without a delay,
+/// and the text is wrapped to the content width of the running [`Presentation`](crate::Presentation),
+/// if it has one.
+///
+/// With a zero delay, the text is printed instantly: each run of text between pause markers is written in one go,
+/// without flushing or toggling raw mode after every character, and regardless of the direction.
+pub(crate) fn typewrite(
+    f: &mut impl fmt::Write,
+    text: &str,
+    style: Option<ContentStyle>,
+    typing: Typing,
+) -> fmt::Result {
+    typewrite_styled(f, text, |_, _| style, typing, &mut |_| {})
+}
+
+/// Like [`typewrite`], but styles each character with `style_at`,
+/// which is given the index of the character among the printed characters of `text`, and how many there are.
+/// `on_char` is called with each character once it's been printed.
+pub(crate) fn typewrite_styled(
+    f: &mut impl fmt::Write,
+    text: &str,
+    style_at: impl Fn(usize, usize) -> Option<ContentStyle>,
+    typing: Typing,
+    on_char: &mut dyn FnMut(char),
+) -> fmt::Result {
+    typewrite_segments(
+        f,
+        text::split_escapes(markers::parse(&text::normalize_newlines(text))),
+        style_at,
+        typing,
+        on_char,
+    )
+}
+
+/// Like [`typewrite_styled`], but types segments that were already parsed from text,
+/// so that their text isn't parsed for pause markers again.
+pub(crate) fn typewrite_segments(
+    f: &mut impl fmt::Write,
+    segments: Vec<Segment>,
+    style_at: impl Fn(usize, usize) -> Option<ContentStyle>,
+    typing: Typing,
+    on_char: &mut dyn FnMut(char),
+) -> fmt::Result {
+    if runner::is_interrupted() {
+        return Ok(());
+    }
+
+    // Raw mode stays enabled for the whole command rather than being toggled around every delay,
+    // and the session restores it even if something panics partway through.
+    let _session = session::start();
+    let typing = match PROFILE.with(Cell::get) {
+        Some(profile) => typing.or(profile),
+        None => typing,
+    };
+    let segments = layout::arrange(segments);
+    let mut pacing = Pacing::new(typing, &segments, on_char);
+
+    if typing.is_instant() {
+        for segment in segments {
+            match segment {
+                Segment::Text(content) => write_runs(f, &content, &style_at, &mut pacing)?,
+                Segment::Pause(duration) => pacing.pause(duration),
+                Segment::Raw(raw) => f.write_str(&raw)?,
+            }
+        }
+
+        return Ok(());
+    }
+
+    // Output is batched while typing with a flush interval, so it's only written when it's flushed.
+    let was_batching = output::output_batching();
+
+    if typing.flush_interval.is_some() {
+        output::set_output_batching(true);
+    }
+
+    let result = match typing.direction() {
+        Direction::LeftToRight => typewrite_forward(f, segments, &style_at, &mut pacing),
+        Direction::RightToLeft => {
+            split_lines(segments)
+                .into_iter()
+                .enumerate()
+                .try_for_each(|(index, line)| {
+                    if index > 0 {
+                        text::write_newline(f)?;
+                    }
+
+                    typewrite_line_reversed(f, line, &style_at, &mut pacing)
+                })
+        }
+    };
+
+    output::set_output_batching(was_batching);
+
+    result
+}
+
+/// Types segments from their first character to their last.
+fn typewrite_forward(
+    f: &mut impl fmt::Write,
+    segments: Vec<Segment>,
+    style_at: &impl Fn(usize, usize) -> Option<ContentStyle>,
+    pacing: &mut Pacing<'_>,
+) -> fmt::Result {
+    for segment in segments {
+        match segment {
+            Segment::Text(content) => {
+                for grapheme in content.graphemes(true) {
+                    write_grapheme(f, grapheme, style_at(pacing.typed, pacing.total))?;
+                    pacing.ring(f, grapheme)?;
+                    pacing.pause_after(grapheme);
+                }
+            }
+            Segment::Pause(duration) => pacing.pause(duration),
+            Segment::Raw(raw) => f.write_str(&raw)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a single grapheme with an optional style, translating `\n` for raw mode.
+pub(crate) fn write_grapheme(
+    f: &mut impl fmt::Write,
+    grapheme: &str,
+    style: Option<ContentStyle>,
+) -> fmt::Result {
+    match style {
+        _ if grapheme == "\n" => text::write_newline(f),
+        Some(style) => PrintStyledContent(StyledContent::new(style, grapheme)).write_ansi(f),
+        None => f.write_str(grapheme),
+    }
+}
+
+/// Writes a string with an optional style, translating each `\n` for raw mode.
+fn write_str(f: &mut impl fmt::Write, content: &str, style: Option<ContentStyle>) -> fmt::Result {
+    for (index, line) in content.split('\n').enumerate() {
+        if index > 0 {
+            text::write_newline(f)?;
+        }
+
+        match style {
+            Some(style) => PrintStyledContent(StyledContent::new(style, line)).write_ansi(f)?,
+            None => f.write_str(line)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes `content` all at once, in runs of characters that share a style.
+fn write_runs(
+    f: &mut impl fmt::Write,
+    content: &str,
+    style_at: &impl Fn(usize, usize) -> Option<ContentStyle>,
+    pacing: &mut Pacing<'_>,
+) -> fmt::Result {
+    let mut run = String::new();
+    let mut run_style = None;
+
+    for grapheme in content.graphemes(true) {
+        let style = match grapheme {
+            "\n" => run_style,
+            _ => style_at(pacing.typed, pacing.total),
+        };
+
+        if style != run_style && !run.is_empty() {
+            write_str(f, &mem::take(&mut run), run_style)?;
+        }
+
+        run_style = style;
+        run.push_str(grapheme);
+        pacing.after(grapheme);
+    }
+
+    write_str(f, &run, run_style)
+}
+
+/// Splits segments into lines, dropping the line breaks between them.
+fn split_lines(segments: Vec<Segment>) -> Vec<Vec<Segment>> {
+    let mut lines = vec![Vec::new()];
+
+    for segment in segments {
+        let Segment::Text(content) = segment else {
+            lines
+                .last_mut()
+                .expect("there should be a line")
+                .push(segment);
+            continue;
+        };
+
+        for (index, line) in content.split('\n').enumerate() {
+            if index > 0 {
+                lines.push(Vec::new());
+            }
+
+            if !line.is_empty() {
+                lines
+                    .last_mut()
+                    .expect("there should be a line")
+                    .push(Segment::Text(line.to_string()));
+            }
+        }
+    }
+
+    lines
+}
+
+/// Types a single line from its last grapheme to its first, leaving the cursor at its end.
+///
+/// The cursor is moved by the width of each grapheme, so wide characters are typed in place too.
+fn typewrite_line_reversed(
+    f: &mut impl fmt::Write,
+    line: Vec<Segment>,
+    style_at: &impl Fn(usize, usize) -> Option<ContentStyle>,
+    pacing: &mut Pacing<'_>,
+) -> fmt::Result {
+    let width: usize = line
+        .iter()
+        .map(|segment| match segment {
+            Segment::Text(content) => layout::width(content),
+            _ => 0,
+        })
+        .sum();
+    let mut remaining: usize = line
+        .iter()
+        .map(|segment| match segment {
+            Segment::Text(content) => content.chars().count(),
+            _ => 0,
+        })
+        .sum();
+    let line_start = pacing.typed;
+    let mut last_width = None;
+
+    for segment in &line {
+        if let Segment::Raw(raw) = segment {
+            f.write_str(raw)?;
+        }
+    }
+
+    for segment in line.iter().rev() {
+        match segment {
+            Segment::Text(content) => {
+                for grapheme in content.graphemes(true).rev() {
+                    let grapheme_width = layout::width(grapheme);
+
+                    remaining -= grapheme.chars().count();
+
+                    // The cursor is either at the start of the line or right after the grapheme typed last.
+                    match last_width {
+                        None => move_right(f, width.saturating_sub(grapheme_width))?,
+                        Some(last_width) => move_left(f, last_width + grapheme_width)?,
+                    }
+
+                    write_grapheme(f, grapheme, style_at(line_start + remaining, pacing.total))?;
+                    pacing.ring(f, grapheme)?;
+                    pacing.pause_after(grapheme);
+
+                    last_width = Some(grapheme_width);
+                }
+            }
+            Segment::Pause(duration) => pacing.pause(*duration),
+            Segment::Raw(_) => {}
+        }
+    }
+
+    move_right(f, width.saturating_sub(last_width.unwrap_or(width)))
+}
+
+/// Moves the cursor `columns` to the left, doing nothing for zero columns.
+fn move_left(f: &mut impl fmt::Write, columns: usize) -> fmt::Result {
+    if columns == 0 {
+        return Ok(());
+    }
+
+    MoveLeft(u16::try_from(columns).unwrap_or(u16::MAX)).write_ansi(f)
+}
+
+/// Moves the cursor `columns` to the right, doing nothing for zero columns.
+fn move_right(f: &mut impl fmt::Write, columns: usize) -> fmt::Result {
+    if columns == 0 {
+        return Ok(());
+    }
+
+    MoveRight(u16::try_from(columns).unwrap_or(u16::MAX)).write_ansi(f)
+}
@@ -1,5 +1,12 @@
+use clp::crossterm::event::KeyCode;
 use clp::crossterm::style::{Print, Stylize};
-use clp::{slide, TypewriterPrint, TypewriterPrintStyledContent};
+use clp::crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use clp::{
+    capture_output, quit_requested, set_image_protocol, set_quit_keys, slide, BarChart,
+    BeforeAfter, Corner, Countdown, ImageProtocol, Overflow, Presentation, ScrollingLog, Slide,
+    SlideImage, Splash, TextBox, TypewriterMarkdown, TypewriterPrint, TypewriterPrintStyledContent,
+    Typing,
+};
 use std::time::Duration;
 
 #[test]
@@ -20,3 +27,371 @@ fn presentation() {
     ))
     .expect("the second slide should appear");
 }
+
+#[test]
+fn splash_quit() {
+    set_quit_keys(&[KeyCode::Enter]);
+
+    let result = Presentation::new()
+        .splash(Splash::default())
+        .slide(|| panic!("the first slide shouldn't be shown"))
+        .run();
+
+    set_quit_keys(&[]);
+    result.expect("the presentation should stop on the splash screen");
+    assert!(quit_requested());
+}
+
+#[test]
+fn headless_newlines() {
+    let slide = Slide::new(|| slide!(TypewriterPrint("Hello,\nworld!", Duration::from_secs(60))));
+    // Rendering headlessly from inside a session, which has raw mode enabled, shouldn't change the line breaks.
+    let raw = enable_raw_mode().is_ok();
+    let rendered = slide.render_to_string(false);
+
+    if raw {
+        disable_raw_mode().expect("raw mode should be disabled");
+    }
+
+    assert_eq!(rendered.expect("the slide should render"), "Hello,\nworld!");
+}
+
+#[test]
+fn pause_markers() {
+    let slide = Slide::new(|| {
+        slide!(TypewriterPrint(
+            "This slide pauses here{{pause:500}}, but not \\{{pause:500}} here.",
+            Duration::from_secs(60),
+        ))
+    });
+
+    assert_eq!(
+        slide
+            .render_to_string(false)
+            .expect("the slide should render"),
+        "This slide pauses here, but not {{pause:500}} here."
+    );
+}
+
+#[test]
+fn bar_chart() {
+    let chart = Slide::new(|| {
+        slide!(BarChart::new(
+            vec![("Rust", 80.0), ("Go", 35.0), ("C", -10.0), ("Zero", 0.0)],
+            8,
+        ))
+    });
+
+    assert_eq!(
+        chart
+            .render_to_string(false)
+            .expect("the chart should render"),
+        "Rust ████████ 80\nGo   ███▌ 35\nC    ░ -10\nZero  0\n"
+    );
+
+    // The headless terminal is 80 columns wide, which leaves 76 for the bar next to its label and value.
+    let wide = Slide::new(|| slide!(BarChart::new(vec![("A", 1.0)], 200)));
+
+    assert_eq!(
+        wide.render_to_string(false)
+            .expect("the chart should render"),
+        format!("A {} 1\n", "█".repeat(76))
+    );
+}
+
+#[test]
+fn scrolling_log() {
+    let slide = Slide::new(|| {
+        slide!(ScrollingLog(
+            vec![
+                "a{{pause:10}}b".to_string(),
+                "\\{{pause:5}}".to_string(),
+                "漢".repeat(41),
+                "last".to_string(),
+            ],
+            2,
+            Duration::from_secs(60),
+        ))
+    });
+    let wide = "漢".repeat(40);
+
+    assert_eq!(
+        slide
+            .render_to_string(true)
+            .expect("the slide should render"),
+        format!(
+            "\u{1b}[2Jab\n{{{{pause:5}}}}\n\
+             \u{1b}[2F\u{1b}[K{{{{pause:5}}}}\n\u{1b}[K{wide}\n\
+             \u{1b}[2F\u{1b}[K{wide}\n\u{1b}[Klast\n"
+        )
+    );
+}
+
+#[test]
+fn right_to_left() {
+    let slide = Slide::new(|| {
+        slide!(TypewriterPrint(
+            "Fill in\nbackward",
+            Typing::new(Duration::from_secs(60)).right_to_left(),
+        ))
+    });
+
+    assert_eq!(
+        slide
+            .render_to_string(true)
+            .expect("the slide should render"),
+        "\u{1b}[2J\
+         \u{1b}[6Cn\u{1b}[2Di\u{1b}[2D \u{1b}[2Dl\u{1b}[2Dl\u{1b}[2Di\u{1b}[2DF\u{1b}[6C\n\
+         \u{1b}[7Cd\u{1b}[2Dr\u{1b}[2Da\u{1b}[2Dw\u{1b}[2Dk\u{1b}[2Dc\u{1b}[2Da\u{1b}[2Db\u{1b}[7C"
+    );
+}
+
+#[test]
+fn before_after() {
+    let before = format!(
+        "{}{{{{pause:500}}}}\r\n\u{1b}[1mbold\u{1b}[22m\n{}",
+        "x".repeat(75),
+        "y".repeat(90),
+    );
+    let slide = Slide::new(move || slide!(BeforeAfter(&before, "after")));
+
+    assert_eq!(
+        slide
+            .render_to_string(true)
+            .expect("the slide should render"),
+        format!(
+            "\u{1b}[2J{}\n\u{1b}[1mbold\u{1b}[22m\n{}\
+             \u{1b}[3F\u{1b}[1G\u{1b}7\u{1b}[K\
+             \u{1b}[1E\u{1b}[2K\u{1b}[1E\u{1b}[2K\u{1b}[1E\u{1b}[2K\u{1b}8after",
+            "x".repeat(75),
+            "y".repeat(90),
+        )
+    );
+}
+
+#[test]
+fn markdown() {
+    let slide = Slide::new(|| {
+        slide!(TypewriterMarkdown(
+            "Call `unwrap` **only** when *sure*, not 2 * 3 or \\*escaped\\*.",
+            Duration::from_secs(60),
+        ))
+    });
+
+    assert_eq!(
+        slide
+            .render_to_string(false)
+            .expect("the slide should render"),
+        "Call unwrap only when sure, not 2 * 3 or *escaped*."
+    );
+    assert_eq!(
+        slide
+            .render_to_string(true)
+            .expect("the slide should render"),
+        "\u{1b}[2JCall \u{1b}[38;5;14munwrap\u{1b}[39m \u{1b}[1monly\u{1b}[22m when \
+         \u{1b}[3msure\u{1b}[23m, not 2 * 3 or *escaped*."
+    );
+
+    let unclosed = Slide::new(|| slide!(TypewriterMarkdown("*a `b* c`", Duration::from_secs(60))));
+
+    assert_eq!(
+        unclosed
+            .render_to_string(true)
+            .expect("the slide should render"),
+        "\u{1b}[2J\u{1b}[3ma \u{1b}[38;5;14mb* c\u{1b}[39m\u{1b}[23m"
+    );
+}
+
+#[test]
+fn markdown_presentation() {
+    let mut presentation = Presentation::from_markdown(
+        "\n# Hello, world!\n\nThis is a **quick** deck.\n---\n\n\n---\nNo heading\n---\n## Goodbye",
+        Duration::ZERO,
+    );
+    let mut slides = Vec::new();
+
+    assert_eq!(presentation.len(), 3);
+    assert_eq!(
+        presentation
+            .estimate_summary()
+            .lines()
+            .take(3)
+            .map(|line| line[5..45].trim_end().to_string())
+            .collect::<Vec<_>>(),
+        ["Hello, world!", "No heading", "Goodbye"]
+    );
+
+    for _ in 0..presentation.len() {
+        let (result, written) =
+            capture_output(|| presentation.render_current()).expect("the slide should be captured");
+        let current = presentation.current();
+
+        result.expect("the slide should render");
+        slides.push(String::from_utf8_lossy(&written).into_owned());
+
+        while presentation.current() == current && presentation.advance() {}
+    }
+
+    assert!(slides[0].contains("\u{1b}[1mHello, world!\u{1b}[22m"));
+    assert!(slides[0].contains("This is a \u{1b}[1mquick\u{1b}[22m deck."));
+    assert!(slides[1].contains("No heading"));
+    assert!(slides[2].contains("\u{1b}[1mGoodbye\u{1b}[22m"));
+    assert!(slides.iter().all(|slide| !slide.contains('#')));
+}
+
+#[test]
+fn text_box() {
+    let render = |content: &'static str, overflow: Overflow, ansi: bool| {
+        Slide::new(move || {
+            slide!(TextBox {
+                content,
+                width: 9,
+                height: 4,
+                typing: Typing::new(Duration::from_secs(60)),
+                overflow,
+            })
+        })
+        .render_to_string(ansi)
+        .expect("the box should render")
+    };
+    let border = "┌───────┐\n│       │\n│       │\n└───────┘\n";
+    let ansi_border =
+        "\u{1b}[2J┌───────┐\n\u{1b}[1G│       │\n\u{1b}[1G│       │\n\u{1b}[1G└───────┘\n\u{1b}[3F";
+
+    assert_eq!(
+        render("abcde fghij", Overflow::Truncate, true),
+        format!("{ansi_border}\u{1b}[3Gabcde\u{1b}[1E\u{1b}[3Gfghij\u{1b}[2E\u{1b}[1G")
+    );
+    assert_eq!(
+        render("漢字漢字漢", Overflow::Truncate, true),
+        format!("{ansi_border}\u{1b}[3G漢字\u{1b}[1E\u{1b}[3G漢字…\u{1b}[2E\u{1b}[1G")
+    );
+    assert_eq!(
+        render("one two three four five", Overflow::Truncate, false),
+        format!("{border}onetwo…")
+    );
+    assert_eq!(
+        render("one two three four five", Overflow::Paginate, false),
+        format!("{border}onetwo          threefour          five")
+    );
+    assert_eq!(
+        Slide::new(|| slide!(TextBox::new("tall", 9, 30)))
+            .render_to_string(false)
+            .expect("the message should render"),
+        "Terminal too small: needs at least 9x30, but it's 80x24.\n"
+    );
+}
+
+#[test]
+fn validation() {
+    let presentation = Presentation::new()
+        .slide(|| slide!(TypewriterPrint("Fine", Duration::from_secs(60))))
+        .slide(|| panic!("broken slide"))
+        .slide_with_profile("missing", || {
+            slide!(TypewriterPrint("Fine", Typing::inherit()))
+        });
+    let failures = presentation.validate();
+
+    assert_eq!(
+        failures
+            .iter()
+            .map(|failure| failure.index)
+            .collect::<Vec<_>>(),
+        [1, 2]
+    );
+    assert_eq!(failures[0].message, "broken slide");
+}
+
+#[test]
+fn snapshot() {
+    let slide = Slide::new(|| {
+        slide!(
+            TypewriterPrint("Hello, {{pause:1000}}", Duration::from_secs(60)),
+            TypewriterPrintStyledContent("world".bold(), Duration::from_secs(60)),
+            Print("!"),
+        )
+    });
+
+    assert_eq!(
+        slide
+            .render_to_string(false)
+            .expect("the slide should render"),
+        "Hello, world!"
+    );
+    assert!(slide
+        .render_to_string(true)
+        .expect("the slide should render")
+        .contains("\u{1b}[1m"));
+}
+
+#[test]
+fn kitty_image() {
+    let mut small = SlideImage::new(b"\x89PNG\r\n\x1a\nab".to_vec());
+    let mut large = SlideImage::new([&b"\x89PNG\r\n\x1a\n"[..], &[0; 4000]].concat());
+
+    small.fallback = Some("[a small image]".to_string());
+    large.columns = Some(10);
+    set_image_protocol(ImageProtocol::Kitty);
+
+    let fallback = {
+        let small = small.clone();
+
+        Slide::new(move || slide!(small.clone()))
+    };
+
+    assert_eq!(
+        fallback
+            .render_to_string(false)
+            .expect("the fallback should render"),
+        "[a small image]\n"
+    );
+
+    let (result, written) = capture_output(|| slide!(wait: none; small.clone(), large.clone()))
+        .expect("the images should be captured");
+    let written = String::from_utf8_lossy(&written);
+
+    set_image_protocol(ImageProtocol::Auto);
+    result.expect("the images should be shown");
+    assert!(written.contains("\u{1b}_Ga=T,f=100,m=0;iVBORw0KGgphYg==\u{1b}\\"));
+    assert!(written.contains(&format!(
+        "\u{1b}_Ga=T,f=100,c=10,m=1;iVBORw0KGgo{}\u{1b}\\\u{1b}_Gm=0;{}\u{1b}\\",
+        "A".repeat(4096 - 11),
+        "A".repeat(5344 - 4096),
+    )));
+}
+
+#[test]
+fn countdown() {
+    let ((), written) = capture_output(|| {
+        slide!(
+            wait: none;
+            Countdown(Duration::from_secs(60), Corner::TopRight),
+            TypewriterPrint("First", Duration::from_millis(1)),
+        )
+        .expect("the first slide should appear");
+        slide!(wait: none; TypewriterPrint("Second", Duration::from_millis(250)))
+            .expect("the second slide should appear");
+    })
+    .expect("the slides should be captured");
+    let written = String::from_utf8_lossy(&written);
+    let (first, second) = written
+        .rsplit_once("\u{1b}[2J")
+        .expect("the second slide should be written");
+
+    assert!(first.contains("01:00"));
+    assert!(!second.contains(':'));
+
+    let headless = Slide::new(|| {
+        slide!(
+            Countdown(Duration::from_secs(60), Corner::TopRight),
+            TypewriterPrint("First", Duration::from_secs(60)),
+        )
+    });
+
+    assert_eq!(
+        headless
+            .render_to_string(true)
+            .expect("the slide should render"),
+        "\u{1b}[2JFirst"
+    );
+}